@@ -0,0 +1,27 @@
+/** Base class of the errors thrown by encore operations */
+export class IrongateError extends Error {
+    constructor(message, operation) {
+        super(message);
+        /** Registry id of the operation that failed, e.g. `base64_decode`, or the class name when
+         * its constructor rejected the options */
+        this.operation = operation;
+    }
+}
+IrongateError.prototype.name = "IrongateError";
+
+/** The input could not be decoded */
+export class DecodeError extends IrongateError {
+    constructor(message, operation, offset) {
+        super(message, operation);
+        /** Position of the offending input byte, when the decoder reports it */
+        this.offset = offset;
+    }
+}
+DecodeError.prototype.name = "DecodeError";
+
+/** The options passed to an operation constructor are invalid */
+export class InvalidOptionsError extends IrongateError {}
+InvalidOptionsError.prototype.name = "InvalidOptionsError";
+
+export class EncryptionError extends IrongateError {}
+EncryptionError.prototype.name = "EncryptionError";
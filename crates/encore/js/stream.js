@@ -0,0 +1,15 @@
+/** Feed every chunk of `stream` to `hasher` and resolve with its digest */
+export async function hashStream(hasher, stream) {
+    const reader = stream.getReader();
+    try {
+        for (;;) {
+            const { done, value } = await reader.read();
+            if (done) {
+                return hasher.digest();
+            }
+            hasher.update(value);
+        }
+    } finally {
+        reader.releaseLock();
+    }
+}
@@ -0,0 +1,9 @@
+import type { InitInput, InitOutput } from "./encore.js";
+
+export * from "./encore.js";
+export { applyAsync, IApplyAsync } from "./pool.js";
+
+/** Must resolve before any class is used, defaults to loading `encore_bg.wasm` next to this file */
+export declare function init(input?: InitInput | Promise<InitInput>): Promise<InitOutput>;
+
+export default init;
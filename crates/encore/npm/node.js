@@ -0,0 +1,18 @@
+import { readFile } from "node:fs/promises";
+// The glue and the module are those of the `web` build
+import initWasm from "../web/encore.js";
+
+export * from "../web/encore.js";
+export { applyAsync } from "./pool.js";
+
+let ready;
+
+/** Read and instantiate the module on first call, later calls share the same promise */
+export function init(input) {
+    ready ??= initWasm({
+        module_or_path: input ?? readFile(new URL("../web/encore_bg.wasm", import.meta.url)),
+    });
+    return ready;
+}
+
+export default init;
@@ -0,0 +1,58 @@
+// Turn the wasm-pack `web` output into a single package with conditional exports.
+// Usage: node crates/encore/npm/package.mjs <out-dir>
+import { copyFileSync, mkdirSync, readFileSync, rmSync, writeFileSync } from "node:fs";
+import { dirname, join } from "node:path";
+import { fileURLToPath } from "node:url";
+
+const npm = dirname(fileURLToPath(import.meta.url));
+const out = process.argv[2];
+
+const manifest = readFileSync(join(npm, "..", "Cargo.toml"), "utf8");
+const field = (name) => manifest.match(new RegExp(`^${name} = "(.*)"`, "m"))[1];
+
+// Browsers, bundlers and Deno use the `web` build as is. The `node` directory only holds the
+// wrapper whose `init` reads `../web/encore_bg.wasm` and the worker_threads side of the pool.
+const targets = {
+    web: { wrapper: "web.js", spawn: "spawn.web.js" },
+    node: { wrapper: "node.js", spawn: "spawn.node.js" },
+};
+
+// wasm-pack ignores everything in its output directory
+rmSync(join(out, "web", ".gitignore"), { force: true });
+copyFileSync(join(npm, "index.d.ts"), join(out, "web", "index.d.ts"));
+copyFileSync(join(npm, "pool.d.ts"), join(out, "web", "pool.d.ts"));
+for (const [target, { wrapper, spawn }] of Object.entries(targets)) {
+    const dir = join(out, target);
+    mkdirSync(dir, { recursive: true });
+    copyFileSync(join(npm, wrapper), join(dir, "index.js"));
+    copyFileSync(join(npm, "pool.js"), join(dir, "pool.js"));
+    copyFileSync(join(npm, "worker.js"), join(dir, "worker.js"));
+    copyFileSync(join(npm, spawn), join(dir, "spawn.js"));
+}
+
+// Both targets export the same API, declared next to the glue
+const entry = (target) => ({ types: "./web/index.d.ts", default: `./${target}/index.js` });
+
+const pkg = {
+    name: field("name"),
+    version: field("version"),
+    description: "WASM bindings for irongate/encore crate",
+    license: field("license"),
+    repository: field("repository"),
+    type: "module",
+    files: Object.keys(targets),
+    types: "./web/index.d.ts",
+    // Nothing runs on import, the module is instantiated by `init`
+    sideEffects: false,
+    exports: {
+        ".": {
+            // `require` loads the same ES module, which needs Node.js 20.19 or later
+            node: entry("node"),
+            default: entry("web"),
+        },
+        "./web": entry("web"),
+        "./node": entry("node"),
+        "./web/encore_bg.wasm": "./web/encore_bg.wasm",
+    },
+};
+writeFileSync(join(out, "package.json"), JSON.stringify(pkg, null, 4) + "\n");
@@ -0,0 +1,15 @@
+export interface IApplyAsync {
+    /** Rejects with `signal.reason`, a running job is stopped by terminating its worker */
+    signal?: AbortSignal;
+    /** Transfer `input.buffer` to the worker instead of copying it, detaching it here */
+    transfer?: boolean;
+}
+
+type Step = { op: string; options?: Record<string, unknown> };
+
+/** Run an operation, a `Recipe` or recipe steps on a worker thread */
+export declare function applyAsync(
+    operation: { toJSON(): Step | Step[] } | Step | Step[],
+    input: Uint8Array,
+    options?: IApplyAsync
+): Promise<Uint8Array>;
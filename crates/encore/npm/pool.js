@@ -0,0 +1,136 @@
+// Worker pool behind `applyAsync`, every worker instantiates its own copy of the module and runs
+// the job as a `Recipe`, so operations cross over as their `toJSON` steps.
+import { DecodeError, EncryptionError, InvalidOptionsError, IrongateError } from "./index.js";
+import { spawn } from "./spawn.js";
+
+const errors = { DecodeError, EncryptionError, InvalidOptionsError, IrongateError };
+const size = Math.max(1, Math.min(4, (globalThis.navigator?.hardwareConcurrency ?? 2) - 1));
+const idle = [];
+const queue = [];
+let spawned = 0;
+
+export function applyAsync(operation, input, { signal, transfer = false } = {}) {
+    const steps = [].concat(typeof operation?.toJSON === "function" ? operation.toJSON() : operation);
+    return new Promise((resolve, reject) => {
+        if (signal?.aborted) {
+            reject(signal.reason);
+            return;
+        }
+        const job = { steps, input, transfer, signal, resolve, reject, worker: undefined, done: false };
+        job.abort = () => cancel(job);
+        signal?.addEventListener("abort", job.abort, { once: true });
+        queue.push(job);
+        schedule();
+    });
+}
+
+function schedule() {
+    while (queue.length > 0) {
+        let worker = idle.pop();
+        if (worker === undefined) {
+            if (spawned >= size) {
+                return;
+            }
+            worker = start();
+        }
+        run(worker, queue.shift());
+    }
+}
+
+/** The handlers stay for the lifetime of the worker, so a crash is noticed even while it is idle */
+function start() {
+    const worker = spawn({
+        onMessage: (reply) => settle(worker, reply),
+        onExit: (error) => crash(worker, error),
+    });
+    worker.job = undefined;
+    worker.discarded = false;
+    spawned++;
+    return worker;
+}
+
+function run(worker, job) {
+    job.worker = worker;
+    worker.job = job;
+    worker.ref();
+    worker.post({ steps: job.steps, input: job.input }, job.transfer ? [job.input.buffer] : []);
+}
+
+function settle(worker, { output, error }) {
+    const job = worker.job;
+    if (worker.discarded || job === undefined) {
+        return;
+    }
+    worker.job = undefined;
+    worker.unref();
+    idle.push(worker);
+    if (!job.done) {
+        finish(job);
+        if (error === undefined) {
+            job.resolve(output);
+        } else {
+            job.reject(revive(error));
+        }
+    }
+    schedule();
+}
+
+function crash(worker, error) {
+    // Workers terminated by the pool exit as well
+    if (worker.discarded) {
+        return;
+    }
+    const job = worker.job;
+    discard(worker);
+    if (job !== undefined && !job.done) {
+        finish(job);
+        job.reject(error);
+    }
+    schedule();
+}
+
+/** A running job can only be interrupted by terminating its worker */
+function cancel(job) {
+    if (job.done) {
+        return;
+    }
+    finish(job);
+    const index = queue.indexOf(job);
+    if (index >= 0) {
+        queue.splice(index, 1);
+    } else {
+        discard(job.worker);
+    }
+    job.reject(job.signal.reason);
+    // Other jobs on the same signal are cancelled by the next listeners, let them leave the queue
+    // before the freed worker picks one up
+    queueMicrotask(schedule);
+}
+
+function finish(job) {
+    job.done = true;
+    job.signal?.removeEventListener("abort", job.abort);
+}
+
+function discard(worker) {
+    worker.discarded = true;
+    worker.job = undefined;
+    const index = idle.indexOf(worker);
+    if (index >= 0) {
+        idle.splice(index, 1);
+    }
+    worker.terminate();
+    spawned--;
+}
+
+/** Error classes do not survive structured cloning */
+function revive({ name, message, operation, offset }) {
+    switch (errors[name]) {
+        case undefined:
+            return Object.assign(new Error(message), { name });
+        case DecodeError:
+            return new DecodeError(message, operation, offset);
+        default:
+            return new errors[name](message, operation);
+    }
+}
@@ -0,0 +1,23 @@
+// worker_threads side of the pool, idle workers do not keep the process alive
+import { parentPort, Worker } from "node:worker_threads";
+
+export function spawn({ onMessage, onExit }) {
+    const worker = new Worker(new URL("./worker.js", import.meta.url));
+    worker.on("message", onMessage);
+    // An uncaught error is followed by `exit`, which the pool then ignores
+    worker.on("error", onExit);
+    worker.on("exit", (code) => onExit(new Error(`Worker exited with code ${code}`)));
+    worker.unref();
+    return {
+        post: (message, transfer) => worker.postMessage(message, transfer),
+        terminate: () => worker.terminate(),
+        ref: () => worker.ref(),
+        unref: () => worker.unref(),
+    };
+}
+
+export function listen(handler) {
+    parentPort.on("message", (message) =>
+        handler(message, (reply, transfer = []) => parentPort.postMessage(reply, transfer))
+    );
+}
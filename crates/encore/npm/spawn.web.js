@@ -0,0 +1,21 @@
+// Web Worker side of the pool, for browsers, bundlers and Deno
+export function spawn({ onMessage, onExit }) {
+    const worker = new Worker(new URL("./worker.js", import.meta.url), { type: "module" });
+    worker.onmessage = (event) => onMessage(event.data);
+    // Web Workers have no exit event, an uncaught error is the only sign of a crash
+    worker.onerror = (event) => {
+        event.preventDefault();
+        onExit(event.error ?? new Error(event.message));
+    };
+    return {
+        post: (message, transfer) => worker.postMessage(message, transfer),
+        terminate: () => worker.terminate(),
+        ref() {},
+        unref() {},
+    };
+}
+
+export function listen(handler) {
+    self.onmessage = (event) =>
+        handler(event.data, (message, transfer = []) => self.postMessage(message, transfer));
+}
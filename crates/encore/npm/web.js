@@ -0,0 +1,14 @@
+import initWasm from "./encore.js";
+
+export * from "./encore.js";
+export { applyAsync } from "./pool.js";
+
+let ready;
+
+/** Fetch and instantiate the module on first call, later calls share the same promise */
+export function init(input) {
+    ready ??= initWasm(input === undefined ? undefined : { module_or_path: input });
+    return ready;
+}
+
+export default init;
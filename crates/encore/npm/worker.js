@@ -0,0 +1,22 @@
+// Runs the `applyAsync` jobs posted by the pool, one at a time
+import { init, Recipe } from "./index.js";
+import { listen } from "./spawn.js";
+
+const ready = init();
+
+listen(async ({ steps, input }, reply) => {
+    let recipe;
+    try {
+        await ready;
+        recipe = new Recipe(steps);
+        const result = recipe.apply(input);
+        const output = result.output;
+        result.free();
+        reply({ output }, [output.buffer]);
+    } catch (err) {
+        const { name, message, operation, offset } = err;
+        reply({ error: { name, message, operation, offset } });
+    } finally {
+        recipe?.free();
+    }
+});
@@ -0,0 +1,148 @@
+use std::cell::RefCell;
+use std::mem;
+
+use irongate_encore::Operation;
+use js_sys::Uint8Array;
+use wasm_bindgen::prelude::*;
+
+use crate::errors::operation_error;
+
+/// Adds `applyInto` and `applyInPlace` to a class wrapping the operation in `$field`
+macro_rules! buffer_methods {
+    ($class:ident, $field:ident) => {
+        #[wasm_bindgen]
+        impl $class {
+            /// Replace the content of `output` with the result, reusing its allocation. `output`
+            /// is left empty on error and must not be `input`, use `applyInPlace` instead.
+            #[wasm_bindgen(js_name = applyInto)]
+            pub fn apply_into(
+                &self,
+                input: &$crate::buffer::WasmBuffer,
+                output: &mut $crate::buffer::WasmBuffer,
+            ) -> Result<usize, JsValue> {
+                $crate::buffer::apply_into(
+                    <irongate_encore::$class as irongate_encore::OperationInfo>::ID,
+                    &self.$field,
+                    input,
+                    output,
+                )
+            }
+
+            /// Replace the content of `buffer` with the result, left unchanged on error
+            #[wasm_bindgen(js_name = applyInPlace)]
+            pub fn apply_in_place(
+                &self,
+                buffer: &mut $crate::buffer::WasmBuffer,
+            ) -> Result<usize, JsValue> {
+                $crate::buffer::apply_in_place(
+                    <irongate_encore::$class as irongate_encore::OperationInfo>::ID,
+                    &self.$field,
+                    buffer,
+                )
+            }
+        }
+    };
+}
+
+/// Bytes kept in WASM memory so repeated operations skip the copies of `apply`
+#[wasm_bindgen]
+pub struct WasmBuffer {
+    data: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl WasmBuffer {
+    /// Zero-filled buffer of `length` bytes, empty by default
+    #[wasm_bindgen(constructor)]
+    pub fn new(length: Option<usize>) -> WasmBuffer {
+        WasmBuffer { data: vec![0; length.unwrap_or(0)] }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Truncate or zero-extend to `length` bytes, this invalidates previous views
+    pub fn resize(&mut self, length: usize) {
+        self.data.resize(length, 0);
+    }
+
+    /// `Uint8Array` over the bytes, without copying. The view is only valid until the buffer
+    /// changes size or WASM memory grows, so take a new one after every call into the module.
+    pub fn view(&self) -> Uint8Array {
+        // SAFETY: callers must drop the view before the buffer or WASM memory changes, as documented
+        unsafe { Uint8Array::view(&self.data) }
+    }
+
+    /// Replace the content with a copy of `input`
+    #[wasm_bindgen(js_name = copyFrom)]
+    pub fn copy_from(&mut self, input: &[u8]) {
+        self.data.clear();
+        self.data.extend_from_slice(input);
+    }
+
+    /// Copy the bytes out of WASM memory
+    #[wasm_bindgen(js_name = toUint8Array)]
+    pub fn to_uint8_array(&self) -> Vec<u8> {
+        self.data.clone()
+    }
+}
+
+impl WasmBuffer {
+    pub(crate) fn as_slice(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub(crate) fn as_mut_vec(&mut self) -> &mut Vec<u8> {
+        &mut self.data
+    }
+}
+
+thread_local! {
+    /// Allocations released by earlier calls, reused for intermediate results
+    static SPARE: RefCell<Vec<Vec<u8>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Empty vector reusing the allocation of a previous result when one is available
+pub(crate) fn take_spare() -> Vec<u8> {
+    let mut spare = SPARE.with_borrow_mut(Vec::pop).unwrap_or_default();
+    spare.clear();
+    spare
+}
+
+/// Keep the allocation of `spare` for `take_spare`, a recipe run in place needs two at most
+pub(crate) fn keep_spare(spare: Vec<u8>) {
+    SPARE.with_borrow_mut(|spares| {
+        if spares.len() < 2 {
+            spares.push(spare);
+        }
+    });
+}
+
+pub(crate) fn apply_into(
+    operation: &str,
+    op: &(impl Operation + ?Sized),
+    input: &WasmBuffer,
+    output: &mut WasmBuffer,
+) -> Result<usize, JsValue> {
+    output.data.clear();
+    op.execute_into(&input.data, &mut output.data)
+        .map_err(|err| operation_error(operation, err))?;
+    Ok(output.data.len())
+}
+
+pub(crate) fn apply_in_place(
+    operation: &str,
+    op: &(impl Operation + ?Sized),
+    buffer: &mut WasmBuffer,
+) -> Result<usize, JsValue> {
+    let mut output = take_spare();
+    let result = op.execute_into(&buffer.data, &mut output);
+    if result.is_ok() {
+        mem::swap(&mut buffer.data, &mut output);
+    }
+    keep_spare(output);
+    result.map_err(|err| operation_error(operation, err))?;
+    Ok(buffer.data.len())
+}
@@ -1,6 +1,8 @@
-use irongate_encore::Operation;
+use irongate_encore::{Operation, OperationInfo};
 use wasm_bindgen::prelude::*;
 
+use crate::errors::operation_error;
+
 #[wasm_bindgen]
 pub struct Base32HexEncode {
     base32hex_encode: irongate_encore::Base32HexEncode,
@@ -14,10 +16,16 @@ impl Base32HexEncode {
     }
 
     pub fn apply(&self, input: &[u8]) -> Result<Vec<u8>, JsValue> {
-        self.base32hex_encode.execute(input).map_err(|err| JsValue::from_str(&format!("{err:?}")))
+        self.base32hex_encode
+            .execute(input)
+            .map_err(|err| operation_error(irongate_encore::Base32HexEncode::ID, err))
     }
 }
 
+string_methods!(Base32HexEncode, base32hex_encode);
+recipe_step!(Base32HexEncode, base32hex_encode);
+buffer_methods!(Base32HexEncode, base32hex_encode);
+
 #[wasm_bindgen]
 pub struct Base32HexDecode {
     base32hex_decode: irongate_encore::Base32HexDecode,
@@ -31,6 +39,12 @@ impl Base32HexDecode {
     }
 
     pub fn apply(&self, input: &[u8]) -> Result<Vec<u8>, JsValue> {
-        self.base32hex_decode.execute(input).map_err(|err| JsValue::from_str(&format!("{err:?}")))
+        self.base32hex_decode
+            .execute(input)
+            .map_err(|err| operation_error(irongate_encore::Base32HexDecode::ID, err))
     }
 }
+
+string_methods!(Base32HexDecode, base32hex_decode);
+recipe_step!(Base32HexDecode, base32hex_decode);
+buffer_methods!(Base32HexDecode, base32hex_decode);
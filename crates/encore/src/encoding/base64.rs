@@ -1,6 +1,54 @@
-use irongate_encore::Operation;
+use irongate_encore::{Base64Format, Operation, OperationInfo};
+use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
+use crate::errors::{invalid_options, operation_error, options};
+
+#[wasm_bindgen(typescript_custom_section)]
+const IBase64: &'static str = r#"
+interface IBase64 {
+    format?: "standard" | "url" | "mime";
+    pad?: boolean;
+}
+"#;
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsBase64 {
+    format: Option<String>, // Wasm bindgen doesnt support enums yet
+    pad: Option<bool>,
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "IBase64")]
+    pub type IBase64;
+}
+
+fn convert_base64_format(operation: &str, format: Option<String>) -> Result<Base64Format, JsValue> {
+    match format.as_deref() {
+        None | Some("standard") => Ok(Base64Format::Standard),
+        Some("url") => Ok(Base64Format::Url),
+        Some("mime") => Ok(Base64Format::Mime),
+        Some(format) => Err(invalid_options(
+            operation,
+            format_args!("unknown format \"{format}\", expected \"standard\", \"url\" or \"mime\""),
+        )),
+    }
+}
+
+/// Standard alphabet with padding when no options are given
+fn convert_base64_params(
+    operation: &str,
+    params: Option<IBase64>,
+) -> Result<(Base64Format, bool), JsValue> {
+    let Some(params) = params else {
+        return Ok((Base64Format::Standard, true));
+    };
+    let js_base64: JsBase64 = options(operation, params.into())?;
+    Ok((convert_base64_format(operation, js_base64.format)?, js_base64.pad.unwrap_or(true)))
+}
+
 #[wasm_bindgen]
 pub struct Base64Encode {
     base64_encode: irongate_encore::Base64Encode,
@@ -9,20 +57,22 @@ pub struct Base64Encode {
 #[wasm_bindgen]
 impl Base64Encode {
     #[wasm_bindgen(constructor)]
-    pub fn new() -> Base64Encode {
-        Base64Encode {
-            base64_encode: irongate_encore::Base64Encode::new(
-                irongate_encore::Base64Format::Standard,
-                true,
-            ),
-        }
+    pub fn new(params: Option<IBase64>) -> Result<Base64Encode, JsValue> {
+        let (format, pad) = convert_base64_params("Base64Encode", params)?;
+        Ok(Base64Encode { base64_encode: irongate_encore::Base64Encode::new(format, pad) })
     }
 
     pub fn apply(&self, input: &[u8]) -> Result<Vec<u8>, JsValue> {
-        self.base64_encode.execute(input).map_err(|err| JsValue::from_str(&format!("{err:?}")))
+        self.base64_encode
+            .execute(input)
+            .map_err(|err| operation_error(irongate_encore::Base64Encode::ID, err))
     }
 }
 
+string_methods!(Base64Encode, base64_encode);
+recipe_step!(Base64Encode, base64_encode);
+buffer_methods!(Base64Encode, base64_encode);
+
 #[wasm_bindgen]
 pub struct Base64Decode {
     base64_decode: irongate_encore::Base64Decode,
@@ -31,16 +81,18 @@ pub struct Base64Decode {
 #[wasm_bindgen]
 impl Base64Decode {
     #[wasm_bindgen(constructor)]
-    pub fn new() -> Base64Decode {
-        Base64Decode {
-            base64_decode: irongate_encore::Base64Decode::new(
-                irongate_encore::Base64Format::Standard,
-                true,
-            ),
-        }
+    pub fn new(params: Option<IBase64>) -> Result<Base64Decode, JsValue> {
+        let (format, pad) = convert_base64_params("Base64Decode", params)?;
+        Ok(Base64Decode { base64_decode: irongate_encore::Base64Decode::new(format, pad) })
     }
 
     pub fn apply(&self, input: &[u8]) -> Result<Vec<u8>, JsValue> {
-        self.base64_decode.execute(input).map_err(|err| JsValue::from_str(&format!("{err:?}")))
+        self.base64_decode
+            .execute(input)
+            .map_err(|err| operation_error(irongate_encore::Base64Decode::ID, err))
     }
 }
+
+string_methods!(Base64Decode, base64_decode);
+recipe_step!(Base64Decode, base64_decode);
+buffer_methods!(Base64Decode, base64_decode);
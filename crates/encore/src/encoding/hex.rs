@@ -1,8 +1,9 @@
-use irongate_encore::{HexFormat, Operation};
+use irongate_encore::{HexFormat, Operation, OperationInfo};
 use serde::{Deserialize, Serialize};
-use serde_wasm_bindgen::from_value;
 use wasm_bindgen::prelude::*;
 
+use crate::errors::{invalid_options, operation_error, options};
+
 #[wasm_bindgen]
 pub struct HexEncode {
     hex_encode: irongate_encore::HexEncode,
@@ -18,6 +19,7 @@ interface IHexEncode {
 "#;
 
 #[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 struct JsHexEncode {
     format: String, // Wasm bindgen doesnt support enums yet
     prefix: Option<String>,
@@ -34,7 +36,10 @@ fn convert_hex_format(format: String) -> Result<HexFormat, JsValue> {
     match format.as_str() {
         "Upper" => Ok(HexFormat::Upper),
         "Lower" => Ok(HexFormat::Lower),
-        _ => Err(JsValue::from_str("Invalid format")),
+        _ => Err(invalid_options(
+            "HexEncode",
+            format_args!("unknown format \"{format}\", expected \"Upper\" or \"Lower\""),
+        )),
     }
 }
 
@@ -42,9 +47,7 @@ fn convert_hex_format(format: String) -> Result<HexFormat, JsValue> {
 impl HexEncode {
     #[wasm_bindgen(constructor)]
     pub fn new(params: IHexEncode) -> Result<HexEncode, JsValue> {
-        let js_value: JsValue = params.into();
-        let js_hex_encode: JsHexEncode =
-            from_value(js_value).map_err(|_err| JsValue::from_str("Invalid argument"))?;
+        let js_hex_encode: JsHexEncode = options("HexEncode", params.into())?;
         let hex_encode = irongate_encore::HexEncode::new(
             convert_hex_format(js_hex_encode.format)?,
             js_hex_encode.prefix,
@@ -54,10 +57,16 @@ impl HexEncode {
     }
 
     pub fn apply(&self, input: &[u8]) -> Result<Vec<u8>, JsValue> {
-        self.hex_encode.execute(input).map_err(|err| JsValue::from_str(&format!("{err:?}")))
+        self.hex_encode
+            .execute(input)
+            .map_err(|err| operation_error(irongate_encore::HexEncode::ID, err))
     }
 }
 
+string_methods!(HexEncode, hex_encode);
+recipe_step!(HexEncode, hex_encode);
+buffer_methods!(HexEncode, hex_encode);
+
 #[wasm_bindgen]
 pub struct HexDecode {
     hex_decode: irongate_encore::HexDecode,
@@ -71,6 +80,13 @@ interface IHexDecode {
 }
 "#;
 
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsHexDecode {
+    prefix: Option<String>,
+    delimiter: Option<String>,
+}
+
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen(typescript_type = "IHexDecode")]
@@ -81,13 +97,19 @@ extern "C" {
 impl HexDecode {
     #[wasm_bindgen(constructor)]
     pub fn new(params: IHexDecode) -> Result<HexDecode, JsValue> {
-        let js_value: JsValue = params.into();
-        let hex_decode: irongate_encore::HexDecode =
-            from_value(js_value).map_err(|_err| JsValue::from_str("Invalid argument"))?;
+        let js_hex_decode: JsHexDecode = options("HexDecode", params.into())?;
+        let hex_decode =
+            irongate_encore::HexDecode::new(js_hex_decode.prefix, js_hex_decode.delimiter);
         Ok(HexDecode { hex_decode })
     }
 
     pub fn apply(&self, input: &[u8]) -> Result<Vec<u8>, JsValue> {
-        self.hex_decode.execute(input).map_err(|err| JsValue::from_str(&format!("{err:?}")))
+        self.hex_decode
+            .execute(input)
+            .map_err(|err| operation_error(irongate_encore::HexDecode::ID, err))
     }
 }
+
+string_methods!(HexDecode, hex_decode);
+recipe_step!(HexDecode, hex_decode);
+buffer_methods!(HexDecode, hex_decode);
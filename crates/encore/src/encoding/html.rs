@@ -1,6 +1,8 @@
-use irongate_encore::Operation;
+use irongate_encore::{Operation, OperationInfo};
 use wasm_bindgen::prelude::*;
 
+use crate::errors::operation_error;
+
 #[wasm_bindgen]
 pub struct HtmlEncode {
     html_encode: irongate_encore::HtmlEncode,
@@ -14,10 +16,16 @@ impl HtmlEncode {
     }
 
     pub fn apply(&self, input: &[u8]) -> Result<Vec<u8>, JsValue> {
-        self.html_encode.execute(input).map_err(|err| JsValue::from_str(&format!("{err:?}")))
+        self.html_encode
+            .execute(input)
+            .map_err(|err| operation_error(irongate_encore::HtmlEncode::ID, err))
     }
 }
 
+string_methods!(HtmlEncode, html_encode);
+recipe_step!(HtmlEncode, html_encode);
+buffer_methods!(HtmlEncode, html_encode);
+
 #[wasm_bindgen]
 pub struct HtmlDecode {
     html_decode: irongate_encore::HtmlDecode,
@@ -31,6 +39,12 @@ impl HtmlDecode {
     }
 
     pub fn apply(&self, input: &[u8]) -> Result<Vec<u8>, JsValue> {
-        self.html_decode.execute(input).map_err(|err| JsValue::from_str(&format!("{err:?}")))
+        self.html_decode
+            .execute(input)
+            .map_err(|err| operation_error(irongate_encore::HtmlDecode::ID, err))
     }
 }
+
+string_methods!(HtmlDecode, html_decode);
+recipe_step!(HtmlDecode, html_decode);
+buffer_methods!(HtmlDecode, html_decode);
@@ -1,7 +1,9 @@
-use irongate_encore::Operation;
-use serde_wasm_bindgen::from_value;
+use irongate_encore::{Operation, OperationInfo};
+use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
+use crate::errors::{operation_error, options};
+
 #[wasm_bindgen]
 pub struct UrlEncode {
     url_encode: irongate_encore::UrlEncode,
@@ -10,11 +12,22 @@ pub struct UrlEncode {
 #[wasm_bindgen(typescript_custom_section)]
 const IUrlEncode: &'static str = r#"
 interface IUrlEncode {
-    non_ascii: boolean;
-    charset: string;
+    /** Percent-encode every non-ASCII character, false by default */
+    nonAscii?: boolean;
+    /** Characters to percent-encode in addition, none by default */
+    charset?: string;
 }
 "#;
 
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct JsUrlEncode {
+    // `non_ascii` is the spelling of earlier releases
+    #[serde(alias = "non_ascii")]
+    non_ascii: Option<bool>,
+    charset: Option<String>,
+}
+
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen(typescript_type = "IUrlEncode")]
@@ -24,18 +37,29 @@ extern "C" {
 #[wasm_bindgen]
 impl UrlEncode {
     #[wasm_bindgen(constructor)]
-    pub fn new(params: IUrlEncode) -> Result<UrlEncode, JsValue> {
-        let js_value: JsValue = params.into();
-        let url_encode: irongate_encore::UrlEncode =
-            from_value(js_value).map_err(|_err| JsValue::from_str("Invalid argument"))?;
+    pub fn new(params: Option<IUrlEncode>) -> Result<UrlEncode, JsValue> {
+        let js_url_encode = match params {
+            Some(params) => options("UrlEncode", params.into())?,
+            None => JsUrlEncode { non_ascii: None, charset: None },
+        };
+        let url_encode = irongate_encore::UrlEncode::new(
+            js_url_encode.non_ascii.unwrap_or(false),
+            js_url_encode.charset,
+        );
         Ok(UrlEncode { url_encode })
     }
 
     pub fn apply(&self, input: &[u8]) -> Result<Vec<u8>, JsValue> {
-        self.url_encode.execute(input).map_err(|err| JsValue::from_str(&format!("{err:?}")))
+        self.url_encode
+            .execute(input)
+            .map_err(|err| operation_error(irongate_encore::UrlEncode::ID, err))
     }
 }
 
+string_methods!(UrlEncode, url_encode);
+recipe_step!(UrlEncode, url_encode);
+buffer_methods!(UrlEncode, url_encode);
+
 #[wasm_bindgen]
 pub struct UrlDecode {
     url_decode: irongate_encore::UrlDecode,
@@ -49,6 +73,12 @@ impl UrlDecode {
     }
 
     pub fn apply(&self, input: &[u8]) -> Result<Vec<u8>, JsValue> {
-        self.url_decode.execute(input).map_err(|err| JsValue::from_str(&format!("{err:?}")))
+        self.url_decode
+            .execute(input)
+            .map_err(|err| operation_error(irongate_encore::UrlDecode::ID, err))
     }
 }
+
+string_methods!(UrlDecode, url_decode);
+recipe_step!(UrlDecode, url_decode);
+buffer_methods!(UrlDecode, url_decode);
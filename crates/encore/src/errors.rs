@@ -0,0 +1,78 @@
+use std::fmt::Display;
+use std::str::Utf8Error;
+
+use irongate_encore::OperationError;
+use serde::de::DeserializeOwned;
+use serde_wasm_bindgen::from_value;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(typescript_custom_section)]
+const ERRORS: &'static str = r#"
+export class IrongateError extends Error {
+    /** Registry id of the failing operation, or the class whose constructor rejected the options */
+    readonly operation: string;
+}
+export class DecodeError extends IrongateError {
+    readonly offset?: number;
+}
+export class InvalidOptionsError extends IrongateError {}
+export class EncryptionError extends IrongateError {}
+"#;
+
+#[wasm_bindgen(module = "/js/errors.js")]
+extern "C" {
+    #[wasm_bindgen(extends = js_sys::Error, reexport)]
+    pub type IrongateError;
+
+    #[wasm_bindgen(constructor)]
+    fn new(message: &str, operation: &str) -> IrongateError;
+
+    #[wasm_bindgen(extends = IrongateError, extends = js_sys::Error, reexport)]
+    pub type DecodeError;
+
+    #[wasm_bindgen(constructor)]
+    fn new(message: &str, operation: &str, offset: Option<u32>) -> DecodeError;
+
+    #[wasm_bindgen(extends = IrongateError, extends = js_sys::Error, reexport)]
+    pub type InvalidOptionsError;
+
+    #[wasm_bindgen(constructor)]
+    fn new(message: &str, operation: &str) -> InvalidOptionsError;
+
+    #[wasm_bindgen(extends = IrongateError, extends = js_sys::Error, reexport)]
+    pub type EncryptionError;
+
+    #[wasm_bindgen(constructor)]
+    fn new(message: &str, operation: &str) -> EncryptionError;
+}
+
+/// Convert the error of `operation` (its registry id, e.g. `hex_decode`) into an `IrongateError`
+/// subclass, errors from the constructors name the JS class instead
+pub(crate) fn operation_error(operation: &str, err: OperationError) -> JsValue {
+    let message = err.to_string();
+    match err {
+        OperationError::DecodeUtf8Error => DecodeError::new(&message, operation, None).into(),
+        OperationError::DecodeError { offset, .. } => {
+            let offset = offset.and_then(|offset| u32::try_from(offset).ok());
+            DecodeError::new(&message, operation, offset).into()
+        }
+        OperationError::InvalidOptions(_) => InvalidOptionsError::new(&message, operation).into(),
+        OperationError::EncryptionError(_) => EncryptionError::new(&message, operation).into(),
+        _ => IrongateError::new(&message, operation).into(),
+    }
+}
+
+/// Output that is not valid UTF-8, `offset` is the length of the valid prefix
+pub(crate) fn utf8_error(operation: &str, err: Utf8Error) -> JsValue {
+    let message = OperationError::DecodeUtf8Error.to_string();
+    DecodeError::new(&message, operation, u32::try_from(err.valid_up_to()).ok()).into()
+}
+
+pub(crate) fn invalid_options(operation: &str, message: impl Display) -> JsValue {
+    InvalidOptionsError::new(&format!("Invalid options: {message}"), operation).into()
+}
+
+/// Deserialize the options object of `operation`, the option structs deny unknown fields
+pub(crate) fn options<T: DeserializeOwned>(operation: &str, params: JsValue) -> Result<T, JsValue> {
+    from_value(params).map_err(|err| invalid_options(operation, err))
+}
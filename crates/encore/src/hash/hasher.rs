@@ -0,0 +1,117 @@
+use irongate_encore::{HashAlgorithm, MultiHasher, Sha2Version};
+use wasm_bindgen::prelude::*;
+
+use super::{convert_sha2_version, ISha2Hash};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "Md5Hasher | Sha1Hasher | Sha2Hasher")]
+    pub type IHasher;
+
+    #[wasm_bindgen(typescript_type = "ReadableStream<Uint8Array>")]
+    pub type IReadableStream;
+}
+
+#[wasm_bindgen(module = "/js/stream.js")]
+extern "C" {
+    /// Feed every chunk of `stream` to `hasher` and resolve with its digest
+    #[wasm_bindgen(reexport, js_name = hashStream, unchecked_return_type = "Promise<Uint8Array>")]
+    pub fn hash_stream(hasher: &IHasher, stream: &IReadableStream) -> js_sys::Promise;
+}
+
+/// Single digest state shared by the JS hasher classes
+struct Hasher {
+    algorithm: HashAlgorithm,
+    state: MultiHasher,
+}
+
+impl Hasher {
+    fn new(algorithm: HashAlgorithm) -> Self {
+        Hasher { algorithm, state: MultiHasher::new(&[algorithm]) }
+    }
+
+    /// Digest of the data so far, the state is kept so more chunks can follow
+    fn digest(&self) -> Vec<u8> {
+        self.state.clone().finalize().pop().map(|(_, digest)| digest).unwrap_or_default()
+    }
+
+    fn reset(&mut self) {
+        self.state = MultiHasher::new(&[self.algorithm]);
+    }
+}
+
+/// Adds `update`, `digest`, `digestHex` and `reset` to a class wrapping a `Hasher` in `hasher`
+macro_rules! hasher_methods {
+    ($class:ident) => {
+        #[wasm_bindgen]
+        impl $class {
+            pub fn update(&mut self, chunk: &[u8]) {
+                self.hasher.state.update(chunk);
+            }
+
+            pub fn digest(&self) -> Vec<u8> {
+                self.hasher.digest()
+            }
+
+            #[wasm_bindgen(js_name = digestHex)]
+            pub fn digest_hex(&self) -> Result<String, JsValue> {
+                $crate::strings::to_hex(stringify!($class), &self.hasher.digest())
+            }
+
+            pub fn reset(&mut self) {
+                self.hasher.reset();
+            }
+        }
+    };
+}
+
+#[wasm_bindgen]
+pub struct Md5Hasher {
+    hasher: Hasher,
+}
+
+#[wasm_bindgen]
+impl Md5Hasher {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Md5Hasher {
+        Md5Hasher { hasher: Hasher::new(HashAlgorithm::Md5) }
+    }
+}
+
+hasher_methods!(Md5Hasher);
+
+#[wasm_bindgen]
+pub struct Sha1Hasher {
+    hasher: Hasher,
+}
+
+#[wasm_bindgen]
+impl Sha1Hasher {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Sha1Hasher {
+        Sha1Hasher { hasher: Hasher::new(HashAlgorithm::Sha1) }
+    }
+}
+
+hasher_methods!(Sha1Hasher);
+
+#[wasm_bindgen]
+pub struct Sha2Hasher {
+    hasher: Hasher,
+}
+
+#[wasm_bindgen]
+impl Sha2Hasher {
+    #[wasm_bindgen(constructor)]
+    pub fn new(params: ISha2Hash) -> Result<Sha2Hasher, JsValue> {
+        let algorithm = match convert_sha2_version("Sha2Hasher", params)? {
+            Sha2Version::Sha224 => HashAlgorithm::Sha224,
+            Sha2Version::Sha256 => HashAlgorithm::Sha256,
+            Sha2Version::Sha384 => HashAlgorithm::Sha384,
+            Sha2Version::Sha512 => HashAlgorithm::Sha512,
+        };
+        Ok(Sha2Hasher { hasher: Hasher::new(algorithm) })
+    }
+}
+
+hasher_methods!(Sha2Hasher);
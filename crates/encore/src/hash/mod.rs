@@ -1,8 +1,13 @@
-use irongate_encore::{Operation, Sha2Version};
+mod hasher;
+
+pub use hasher::*;
+
+use irongate_encore::{Operation, OperationInfo, Sha2Version};
 use serde::{Deserialize, Serialize};
-use serde_wasm_bindgen::from_value;
 use wasm_bindgen::prelude::*;
 
+use crate::errors::{invalid_options, operation_error, options};
+
 #[wasm_bindgen]
 pub struct Md5Hash {
     md5_hash: irongate_encore::Md5Hash,
@@ -16,10 +21,16 @@ impl Md5Hash {
     }
 
     pub fn apply(&self, input: &[u8]) -> Result<Vec<u8>, JsValue> {
-        self.md5_hash.execute(input).map_err(|err| JsValue::from_str(&format!("{err:?}")))
+        self.md5_hash
+            .execute(input)
+            .map_err(|err| operation_error(irongate_encore::Md5Hash::ID, err))
     }
 }
 
+string_methods!(Md5Hash, md5_hash);
+recipe_step!(Md5Hash, md5_hash);
+buffer_methods!(Md5Hash, md5_hash);
+
 #[wasm_bindgen]
 pub struct Sha1Hash {
     sha1_hash: irongate_encore::Sha1Hash,
@@ -33,10 +44,16 @@ impl Sha1Hash {
     }
 
     pub fn apply(&self, input: &[u8]) -> Result<Vec<u8>, JsValue> {
-        self.sha1_hash.execute(input).map_err(|err| JsValue::from_str(&format!("{err:?}")))
+        self.sha1_hash
+            .execute(input)
+            .map_err(|err| operation_error(irongate_encore::Sha1Hash::ID, err))
     }
 }
 
+string_methods!(Sha1Hash, sha1_hash);
+recipe_step!(Sha1Hash, sha1_hash);
+buffer_methods!(Sha1Hash, sha1_hash);
+
 #[wasm_bindgen]
 pub struct Sha2Hash {
     sha2_hash: irongate_encore::Sha2Hash,
@@ -56,28 +73,46 @@ extern "C" {
 }
 
 #[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 struct JsSha2Hash {
-    version: String,
+    version: Option<String>,
+}
+
+/// Sha256 when no version is given
+pub(crate) fn convert_sha2_version(
+    operation: &str,
+    params: ISha2Hash,
+) -> Result<Sha2Version, JsValue> {
+    let js_params: JsSha2Hash = options(operation, params.into())?;
+    match js_params.version.as_deref() {
+        Some("Sha224") => Ok(Sha2Version::Sha224),
+        None | Some("Sha256") => Ok(Sha2Version::Sha256),
+        Some("Sha384") => Ok(Sha2Version::Sha384),
+        Some("Sha512") => Ok(Sha2Version::Sha512),
+        Some(version) => Err(invalid_options(
+            operation,
+            format_args!(
+                "unknown version \"{version}\", expected \"Sha224\", \"Sha256\", \"Sha384\" or \"Sha512\""
+            ),
+        )),
+    }
 }
 
 #[wasm_bindgen]
 impl Sha2Hash {
     #[wasm_bindgen(constructor)]
     pub fn new(params: ISha2Hash) -> Result<Sha2Hash, JsValue> {
-        let js_value: JsValue = params.into();
-        let js_params: JsSha2Hash =
-            from_value(js_value).map_err(|_err| JsValue::from_str("Invalid argument"))?;
-        let version = match js_params.version.as_str() {
-            "Sha224" => Sha2Version::Sha224,
-            "Sha256" => Sha2Version::Sha256,
-            "Sha384" => Sha2Version::Sha384,
-            "Sha512" => Sha2Version::Sha512,
-            _ => Sha2Version::Sha256,
-        };
+        let version = convert_sha2_version("Sha2Hash", params)?;
         Ok(Sha2Hash { sha2_hash: irongate_encore::Sha2Hash::new(version) })
     }
 
     pub fn apply(&self, input: &[u8]) -> Result<Vec<u8>, JsValue> {
-        self.sha2_hash.execute(input).map_err(|err| JsValue::from_str(&format!("{err:?}")))
+        self.sha2_hash
+            .execute(input)
+            .map_err(|err| operation_error(irongate_encore::Sha2Hash::ID, err))
     }
 }
+
+string_methods!(Sha2Hash, sha2_hash);
+recipe_step!(Sha2Hash, sha2_hash);
+buffer_methods!(Sha2Hash, sha2_hash);
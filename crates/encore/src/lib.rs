@@ -1,12 +1,25 @@
 #![allow(non_snake_case)]
 #![allow(clippy::new_without_default)]
 
+#[macro_use]
+mod buffer;
+#[macro_use]
+mod recipe;
+#[macro_use]
+mod strings;
+
 mod encoding;
+mod errors;
 mod hash;
+mod registry;
 
+pub use buffer::WasmBuffer;
 pub use encoding::base32hex::*;
 pub use encoding::base64::*;
 pub use encoding::hex::*;
 pub use encoding::html::*;
 pub use encoding::url::*;
+pub use errors::{DecodeError, EncryptionError, InvalidOptionsError, IrongateError};
 pub use hash::*;
+pub use recipe::*;
+pub use registry::*;
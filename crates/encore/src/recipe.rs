@@ -0,0 +1,227 @@
+use std::mem;
+
+use irongate_encore::registry::{self, BoxedOperation};
+use irongate_encore::OperationError;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use serde_wasm_bindgen::Serializer;
+use wasm_bindgen::prelude::*;
+
+use crate::buffer::{self, WasmBuffer};
+use crate::errors::{invalid_options, operation_error, options};
+use crate::strings::{to_base64, to_hex, to_string};
+
+#[wasm_bindgen(typescript_custom_section)]
+const IRecipeStep: &'static str = r#"
+interface IRecipeStep {
+    op: string;
+    options?: Record<string, unknown>;
+}
+"#;
+
+/// Adds `toJSON` returning the recipe step of the operation in `$field`
+macro_rules! recipe_step {
+    ($class:ident, $field:ident) => {
+        #[wasm_bindgen]
+        impl $class {
+            /// The `{op, options}` recipe step of this operation, used by `applyAsync`
+            #[wasm_bindgen(js_name = toJSON, unchecked_return_type = "IRecipeStep")]
+            pub fn to_json(&self) -> Result<JsValue, JsValue> {
+                $crate::recipe::to_step(
+                    <irongate_encore::$class as irongate_encore::OperationInfo>::ID,
+                    &self.$field,
+                )
+            }
+        }
+    };
+}
+
+#[derive(Serialize)]
+struct JsRecipeStepRef<'a, T> {
+    op: &'a str,
+    options: &'a T,
+}
+
+pub(crate) fn to_step<T: Serialize>(op: &str, options: &T) -> Result<JsValue, JsValue> {
+    Ok(JsRecipeStepRef { op, options }.serialize(&Serializer::json_compatible())?)
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsRecipeStep {
+    op: String,
+    #[serde(default)]
+    options: Value,
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "IRecipeStep[] | string")]
+    pub type IRecipe;
+
+    #[wasm_bindgen(js_namespace = performance)]
+    fn now() -> f64;
+}
+
+/// Steps from either an array of objects or its JSON serialization
+fn convert_recipe(steps: IRecipe) -> Result<Vec<JsRecipeStep>, JsValue> {
+    match steps.as_string() {
+        Some(json) => serde_json::from_str(&json).map_err(|err| invalid_options("Recipe", err)),
+        None => options("Recipe", steps.into()),
+    }
+}
+
+fn step_error(index: usize, op: &str, err: OperationError) -> JsValue {
+    let message = match err {
+        OperationError::InvalidOptions(message) => message,
+        err => err.to_string(),
+    };
+    invalid_options("Recipe", format_args!("step {index} ({op}): {message}"))
+}
+
+/// Chain of registry operations run on a single input without leaving WASM
+#[wasm_bindgen]
+pub struct Recipe {
+    steps: Vec<(JsRecipeStep, BoxedOperation)>,
+}
+
+#[wasm_bindgen]
+impl Recipe {
+    /// Every step is validated up front, errors name the index of the offending step
+    #[wasm_bindgen(constructor)]
+    pub fn new(steps: IRecipe) -> Result<Recipe, JsValue> {
+        let steps = convert_recipe(steps)?
+            .into_iter()
+            .enumerate()
+            .map(|(index, step)| match registry::create(&step.op, step.options.clone()) {
+                Ok(operation) => Ok((step, operation)),
+                Err(err) => Err(step_error(index, &step.op, err)),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Recipe { steps })
+    }
+
+    /// Errors thrown by a step carry its operation id as `operation`
+    pub fn apply(&self, input: &[u8]) -> Result<RecipeResult, JsValue> {
+        let mut timings = Vec::with_capacity(self.steps.len());
+        let mut output = Vec::new();
+        self.run_into(input, &mut output, Some(&mut timings))?;
+        Ok(RecipeResult { output, timings })
+    }
+
+    /// Same as `applyString` of the operation classes, without timings
+    #[wasm_bindgen(js_name = applyString)]
+    pub fn apply_string(&self, input: &str, lossy: Option<bool>) -> Result<String, JsValue> {
+        to_string("Recipe", self.run(input.as_bytes())?, lossy.unwrap_or(false))
+    }
+
+    #[wasm_bindgen(js_name = applyToHex)]
+    pub fn apply_to_hex(&self, input: &[u8]) -> Result<String, JsValue> {
+        to_hex("Recipe", &self.run(input)?)
+    }
+
+    #[wasm_bindgen(js_name = applyToBase64)]
+    pub fn apply_to_base64(&self, input: &[u8]) -> Result<String, JsValue> {
+        to_base64("Recipe", &self.run(input)?)
+    }
+
+    /// Same as `applyInto` of the operation classes, without timings
+    #[wasm_bindgen(js_name = applyInto)]
+    pub fn apply_into(
+        &self,
+        input: &WasmBuffer,
+        output: &mut WasmBuffer,
+    ) -> Result<usize, JsValue> {
+        self.run_into(input.as_slice(), output.as_mut_vec(), None)?;
+        Ok(output.length())
+    }
+
+    #[wasm_bindgen(js_name = applyInPlace)]
+    pub fn apply_in_place(&self, buffer: &mut WasmBuffer) -> Result<usize, JsValue> {
+        let mut output = buffer::take_spare();
+        let result = self.run_into(buffer.as_slice(), &mut output, None);
+        if result.is_ok() {
+            mem::swap(buffer.as_mut_vec(), &mut output);
+        }
+        buffer::keep_spare(output);
+        result?;
+        Ok(buffer.length())
+    }
+
+    /// The steps the recipe was built from, used by `applyAsync`
+    #[wasm_bindgen(js_name = toJSON, unchecked_return_type = "IRecipeStep[]")]
+    pub fn to_json(&self) -> Result<JsValue, JsValue> {
+        let steps: Vec<_> = self.steps.iter().map(|(step, _)| step).collect();
+        Ok(steps.serialize(&Serializer::json_compatible())?)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.steps.len()
+    }
+}
+
+impl Recipe {
+    fn run(&self, input: &[u8]) -> Result<Vec<u8>, JsValue> {
+        let mut output = Vec::new();
+        self.run_into(input, &mut output, None)?;
+        Ok(output)
+    }
+
+    /// Replace `out` with the result of the chain, left empty on error. The first step reads
+    /// `input` directly, the next ones ping-pong between `out` and a spare buffer. The duration
+    /// of each step is pushed to `timings`.
+    fn run_into(
+        &self,
+        input: &[u8],
+        out: &mut Vec<u8>,
+        mut timings: Option<&mut Vec<f64>>,
+    ) -> Result<(), JsValue> {
+        out.clear();
+        if self.steps.is_empty() {
+            out.extend_from_slice(input);
+            return Ok(());
+        }
+        let mut scratch = buffer::take_spare();
+        let mut result = Ok(());
+        for (index, (step, operation)) in self.steps.iter().enumerate() {
+            let start = timings.is_some().then(now);
+            result = if index == 0 {
+                operation.execute_into(input, out)
+            } else {
+                scratch.clear();
+                operation.execute_into(out, &mut scratch).map(|()| mem::swap(out, &mut scratch))
+            }
+            .map_err(|err| operation_error(&step.op, err));
+            if result.is_err() {
+                out.clear();
+                break;
+            }
+            if let (Some(timings), Some(start)) = (timings.as_deref_mut(), start) {
+                timings.push(now() - start);
+            }
+        }
+        buffer::keep_spare(scratch);
+        result
+    }
+}
+
+#[wasm_bindgen]
+pub struct RecipeResult {
+    output: Vec<u8>,
+    timings: Vec<f64>,
+}
+
+#[wasm_bindgen]
+impl RecipeResult {
+    #[wasm_bindgen(getter)]
+    pub fn output(&self) -> Vec<u8> {
+        self.output.clone()
+    }
+
+    /// Milliseconds spent in each step, in recipe order
+    #[wasm_bindgen(getter)]
+    pub fn timings(&self) -> Vec<f64> {
+        self.timings.clone()
+    }
+}
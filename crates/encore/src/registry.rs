@@ -0,0 +1,113 @@
+use irongate_encore::registry::{self, BoxedOperation, Category};
+use serde::Serialize;
+use serde_json::Value;
+use serde_wasm_bindgen::{from_value, Serializer};
+use wasm_bindgen::prelude::*;
+
+use crate::buffer::{apply_in_place, apply_into, WasmBuffer};
+use crate::errors::{invalid_options, operation_error};
+use crate::recipe::to_step;
+use crate::strings::{to_base64, to_hex, to_string};
+
+#[wasm_bindgen(typescript_custom_section)]
+const IOperationInfo: &'static str = r#"
+interface IOperationInfo {
+    id: string;
+    name: string;
+    category: "encoding" | "hash" | "compression" | "certificate" | "time" | "analysis" | "text";
+    description: string;
+    /** JSON schema of the options accepted by `createOperation` */
+    schema: Record<string, unknown>;
+}
+"#;
+
+#[derive(Serialize)]
+struct JsOperationInfo {
+    id: &'static str,
+    name: &'static str,
+    category: Category,
+    description: &'static str,
+    schema: Value,
+}
+
+/// Every operation `createOperation` accepts, for front-ends rendering option forms
+#[wasm_bindgen(js_name = listOperations, unchecked_return_type = "IOperationInfo[]")]
+pub fn list_operations() -> Result<JsValue, JsValue> {
+    let operations: Vec<_> = registry::operations()
+        .iter()
+        .map(|descriptor| JsOperationInfo {
+            id: descriptor.id,
+            name: descriptor.name,
+            category: descriptor.category,
+            description: descriptor.description,
+            schema: descriptor.options_schema(),
+        })
+        .collect();
+    Ok(operations.serialize(&Serializer::json_compatible())?)
+}
+
+/// Instantiate the operation `id` of `listOperations`, missing options are an empty object
+#[wasm_bindgen(js_name = createOperation)]
+pub fn create_operation(
+    id: &str,
+    #[wasm_bindgen(unchecked_param_type = "Record<string, unknown>")] options: JsValue,
+) -> Result<RegistryOperation, JsValue> {
+    let options: Value = from_value(options).map_err(|err| invalid_options(id, err))?;
+    let operation =
+        registry::create(id, options.clone()).map_err(|err| operation_error(id, err))?;
+    Ok(RegistryOperation { id: id.to_string(), options, operation })
+}
+
+/// Operation built by `createOperation`, errors carry its id as `operation`
+#[wasm_bindgen]
+pub struct RegistryOperation {
+    id: String,
+    options: Value,
+    operation: BoxedOperation,
+}
+
+#[wasm_bindgen]
+impl RegistryOperation {
+    #[wasm_bindgen(getter)]
+    pub fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    pub fn apply(&self, input: &[u8]) -> Result<Vec<u8>, JsValue> {
+        self.operation.execute(input).map_err(|err| operation_error(&self.id, err))
+    }
+
+    #[wasm_bindgen(js_name = applyString)]
+    pub fn apply_string(&self, input: &str, lossy: Option<bool>) -> Result<String, JsValue> {
+        to_string(&self.id, self.apply(input.as_bytes())?, lossy.unwrap_or(false))
+    }
+
+    #[wasm_bindgen(js_name = applyToHex)]
+    pub fn apply_to_hex(&self, input: &[u8]) -> Result<String, JsValue> {
+        to_hex(&self.id, &self.apply(input)?)
+    }
+
+    #[wasm_bindgen(js_name = applyToBase64)]
+    pub fn apply_to_base64(&self, input: &[u8]) -> Result<String, JsValue> {
+        to_base64(&self.id, &self.apply(input)?)
+    }
+
+    #[wasm_bindgen(js_name = applyInto)]
+    pub fn apply_into(
+        &self,
+        input: &WasmBuffer,
+        output: &mut WasmBuffer,
+    ) -> Result<usize, JsValue> {
+        apply_into(&self.id, self.operation.as_ref(), input, output)
+    }
+
+    #[wasm_bindgen(js_name = applyInPlace)]
+    pub fn apply_in_place(&self, buffer: &mut WasmBuffer) -> Result<usize, JsValue> {
+        apply_in_place(&self.id, self.operation.as_ref(), buffer)
+    }
+
+    #[wasm_bindgen(js_name = toJSON, unchecked_return_type = "IRecipeStep")]
+    pub fn to_json(&self) -> Result<JsValue, JsValue> {
+        to_step(&self.id, &self.options)
+    }
+}
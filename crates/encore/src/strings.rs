@@ -0,0 +1,66 @@
+use irongate_encore::{Base64Format, HexFormat, Operation};
+use wasm_bindgen::JsValue;
+
+use crate::errors::{operation_error, utf8_error};
+
+/// Adds `applyString`, `applyToHex` and `applyToBase64` to a class wrapping the operation in `$field`
+macro_rules! string_methods {
+    ($class:ident, $field:ident) => {
+        #[wasm_bindgen]
+        impl $class {
+            /// Apply to the UTF-8 encoding of `input` and decode the output as UTF-8. Invalid
+            /// sequences throw a `DecodeError` unless `lossy` replaces them with U+FFFD.
+            #[wasm_bindgen(js_name = applyString)]
+            pub fn apply_string(
+                &self,
+                input: &str,
+                lossy: Option<bool>,
+            ) -> Result<String, JsValue> {
+                const ID: &str = <irongate_encore::$class as irongate_encore::OperationInfo>::ID;
+                let output = irongate_encore::Operation::execute(&self.$field, input.as_bytes())
+                    .map_err(|err| $crate::errors::operation_error(ID, err))?;
+                $crate::strings::to_string(ID, output, lossy.unwrap_or(false))
+            }
+
+            /// Apply and encode the output as lowercase hex
+            #[wasm_bindgen(js_name = applyToHex)]
+            pub fn apply_to_hex(&self, input: &[u8]) -> Result<String, JsValue> {
+                const ID: &str = <irongate_encore::$class as irongate_encore::OperationInfo>::ID;
+                let output = irongate_encore::Operation::execute(&self.$field, input)
+                    .map_err(|err| $crate::errors::operation_error(ID, err))?;
+                $crate::strings::to_hex(ID, &output)
+            }
+
+            /// Apply and encode the output as padded standard Base64
+            #[wasm_bindgen(js_name = applyToBase64)]
+            pub fn apply_to_base64(&self, input: &[u8]) -> Result<String, JsValue> {
+                const ID: &str = <irongate_encore::$class as irongate_encore::OperationInfo>::ID;
+                let output = irongate_encore::Operation::execute(&self.$field, input)
+                    .map_err(|err| $crate::errors::operation_error(ID, err))?;
+                $crate::strings::to_base64(ID, &output)
+            }
+        }
+    };
+}
+
+pub(crate) fn to_string(operation: &str, output: Vec<u8>, lossy: bool) -> Result<String, JsValue> {
+    match String::from_utf8(output) {
+        Ok(output) => Ok(output),
+        Err(err) if lossy => Ok(String::from_utf8_lossy(err.as_bytes()).into_owned()),
+        Err(err) => Err(utf8_error(operation, err.utf8_error())),
+    }
+}
+
+pub(crate) fn to_hex(operation: &str, output: &[u8]) -> Result<String, JsValue> {
+    let hex = irongate_encore::HexEncode::new(HexFormat::Lower, None, None)
+        .execute(output)
+        .map_err(|err| operation_error(operation, err))?;
+    to_string(operation, hex, false)
+}
+
+pub(crate) fn to_base64(operation: &str, output: &[u8]) -> Result<String, JsValue> {
+    let base64 = irongate_encore::Base64Encode::new(Base64Format::Standard, true)
+        .execute(output)
+        .map_err(|err| operation_error(operation, err))?;
+    to_string(operation, base64, false)
+}
@@ -0,0 +1,477 @@
+use std::{ffi::OsString, iter, path::PathBuf};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use irongate_encore::{
+    AddLineNumbers, Analyze, Asn1Decode, Base32HexDecode, Base32HexEncode, Base64Decode,
+    Base64Encode, Base64Format, Blake3Hash, ChangeCase, HashAlgorithm, HexDecode, HexEncode,
+    HexFormat, HtmlDecode, HtmlEncode, IdentifyHash, Join, Md5Hash, MultiHash, MultiHashFormat,
+    Operation, OperationError, PemDecode, PemEncode, RegexExtract, RegexReplace, RemoveLineNumbers,
+    Reverse, ReverseUnit, Sha1Hash, Sha2Hash, Sha2Version, SortLines, Split, TextCase,
+    TimestampConvert, TimestampFormat, Trim, TrimSide, UniqueLines, UrlDecode, UrlEncode,
+    X509Decode,
+};
+
+/// Separates the steps of a chain, e.g. `irongate decode base64 + hash sha256`. A literal `+`
+/// argument is written `++`, and every longer run of `+` loses one
+pub const STEP_SEPARATOR: &str = "+";
+
+/// Drop the escaping `+` of a `++`, `+++`, ... argument
+fn unescape(arg: &OsString) -> OsString {
+    match arg.to_str() {
+        Some(plus) if plus.len() > 1 && plus.bytes().all(|byte| byte == b'+') => {
+            OsString::from(&plus[1..])
+        }
+        _ => arg.clone(),
+    }
+}
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "irongate",
+    version,
+    about = "Encode, decode, hash and inspect data with irongate-encore",
+    after_help = "Operations can be chained with `+`: irongate decode base64 + hash sha256 --hex\n\
+                  A literal `+` argument is written `++`: irongate text join ++"
+)]
+pub struct Cli {
+    #[command(flatten)]
+    pub io: Io,
+
+    #[command(subcommand)]
+    pub step: Step,
+}
+
+/// Input and output options, accepted anywhere in the chain
+#[derive(Args, Debug, Default)]
+pub struct Io {
+    /// File to read, stdin when omitted or `-`
+    #[arg(short, long, global = true)]
+    pub input: Option<PathBuf>,
+
+    /// File to write, stdout when omitted or `-`
+    #[arg(short, long, global = true)]
+    pub output: Option<PathBuf>,
+
+    /// Write the result as lowercase hex followed by a newline
+    #[arg(long, global = true)]
+    pub hex: bool,
+}
+
+impl Io {
+    fn merge(&mut self, other: Io) {
+        self.input = other.input.or(self.input.take());
+        self.output = other.output.or(self.output.take());
+        self.hex |= other.hex;
+    }
+}
+
+/// Parse arguments (without the binary name) into the I/O options and the chained operations
+pub fn parse<I>(args: I) -> Result<(Io, Vec<Box<dyn Operation>>), clap::Error>
+where
+    I: IntoIterator,
+    I::Item: Into<OsString>,
+{
+    let args: Vec<OsString> = args.into_iter().map(Into::into).collect();
+    let mut io = Io::default();
+    let mut operations = vec![];
+    for step in args.split(|arg| arg == STEP_SEPARATOR) {
+        let step = step.iter().map(unescape);
+        let cli = Cli::try_parse_from(iter::once(OsString::from("irongate")).chain(step))?;
+        io.merge(cli.io);
+        // Options the argument parser cannot check, such as a regex pattern, fail here
+        let operation = cli.step.into_operation().map_err(|err| {
+            clap::Error::raw(clap::error::ErrorKind::ValueValidation, format!("{err}\n"))
+        })?;
+        operations.push(operation);
+    }
+    Ok((io, operations))
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Step {
+    /// Encode the input
+    #[command(subcommand)]
+    Encode(Encode),
+    /// Decode the input
+    #[command(subcommand)]
+    Decode(Decode),
+    /// Hash the input
+    #[command(subcommand)]
+    Hash(Hash),
+    /// Convert a timestamp between formats
+    Time(Time),
+    /// Entropy, byte histogram and file type of the input as JSON
+    Analyze {
+        /// Size of the windows used to locate high entropy regions
+        #[arg(long)]
+        window_size: Option<usize>,
+    },
+    /// Transform the input as text, invalid UTF-8 is kept as is
+    #[command(subcommand)]
+    Text(Text),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Encode {
+    Base64(Base64Args),
+    Base32hex,
+    Hex(HexEncodeArgs),
+    Html,
+    Url {
+        /// Also percent-encode non-ASCII characters
+        #[arg(long)]
+        non_ascii: bool,
+        /// Additional characters to percent-encode
+        #[arg(long)]
+        charset: Option<String>,
+    },
+    Pem {
+        #[arg(long, default_value = "CERTIFICATE")]
+        label: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Decode {
+    Base64(Base64Args),
+    Base32hex,
+    Hex(HexDecodeArgs),
+    Html,
+    Url,
+    Pem {
+        /// Only decode the first block with this label
+        #[arg(long)]
+        label: Option<String>,
+    },
+    /// Render BER/DER or PEM input as an ASN.1 tree
+    Asn1,
+    /// Summarise a DER or PEM certificate as JSON
+    X509,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Hash {
+    Md5,
+    Sha1,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+    Blake3,
+    /// Several digests in a single pass, as JSON unless `--concatenated`
+    Multi {
+        #[arg(required = true, value_enum)]
+        algorithms: Vec<Algorithm>,
+        /// Write the raw digests back to back in the given order
+        #[arg(long)]
+        concatenated: bool,
+    },
+    /// Ranked guesses of the algorithm that produced the input hash, as JSON
+    Identify {
+        /// Check every implemented candidate against this plaintext
+        #[arg(long)]
+        plaintext: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Text {
+    Upper,
+    Lower,
+    Title,
+    SwapCase,
+    Reverse {
+        #[arg(long, value_enum, default_value_t = Unit::Character)]
+        unit: Unit,
+    },
+    /// Sort lines by their bytes
+    Sort {
+        #[arg(long)]
+        reverse: bool,
+        /// Drop repeated lines
+        #[arg(long)]
+        unique: bool,
+    },
+    /// Drop repeated lines, keeping the original order
+    Unique,
+    /// Strip leading and trailing whitespace
+    Trim {
+        #[arg(long, value_enum, default_value_t = Side::Both)]
+        side: Side,
+        /// Trim every line instead of the whole input
+        #[arg(long)]
+        lines: bool,
+    },
+    /// Put every part separated by the delimiter on its own line
+    Split {
+        delimiter: String,
+    },
+    /// Join lines with the delimiter
+    Join {
+        delimiter: String,
+    },
+    /// Prefix every line with its number
+    Number {
+        #[arg(long)]
+        start: Option<usize>,
+    },
+    /// Strip leading line numbers
+    Unnumber,
+    /// Replace regular expression matches, `$1` expands to a capture group
+    Replace {
+        pattern: String,
+        replacement: String,
+        #[arg(short = 'I', long)]
+        ignore_case: bool,
+        /// Only replace the first match
+        #[arg(long)]
+        first: bool,
+    },
+    /// Extract every regular expression match, or its capture groups separated by tabs
+    Extract {
+        pattern: String,
+        #[arg(short = 'I', long)]
+        ignore_case: bool,
+        /// Only write this capture group
+        #[arg(long)]
+        group: Option<usize>,
+        /// Written between matches, a newline by default
+        #[arg(long)]
+        delimiter: Option<String>,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum Algorithm {
+    Md5,
+    Sha1,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+    Blake3,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum Unit {
+    Byte,
+    Character,
+    Line,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum Side {
+    Both,
+    Start,
+    End,
+}
+
+#[derive(Args, Debug)]
+pub struct Base64Args {
+    #[arg(long, value_enum, default_value_t = Base64Variant::Standard)]
+    format: Base64Variant,
+    /// Omit (or do not expect) `=` padding
+    #[arg(long)]
+    no_pad: bool,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Base64Variant {
+    Standard,
+    Url,
+    Mime,
+}
+
+#[derive(Args, Debug)]
+pub struct HexEncodeArgs {
+    /// Use uppercase digits
+    #[arg(long)]
+    upper: bool,
+    #[arg(long)]
+    prefix: Option<String>,
+    #[arg(long)]
+    delimiter: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct HexDecodeArgs {
+    #[arg(long)]
+    prefix: Option<String>,
+    #[arg(long)]
+    delimiter: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct Time {
+    #[arg(long, value_enum)]
+    from: TimeFormat,
+    #[arg(long, value_enum, default_value_t = TimeFormat::Iso8601)]
+    to: TimeFormat,
+    /// `UTC` or an offset such as `+05:30`, used for date strings without an offset and output
+    #[arg(long)]
+    timezone: Option<String>,
+    /// Epoch of Snowflake ids in Unix milliseconds, Twitter's by default
+    #[arg(long)]
+    snowflake_epoch: Option<u64>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum TimeFormat {
+    UnixSeconds,
+    UnixMilliseconds,
+    UnixMicroseconds,
+    UnixNanoseconds,
+    WindowsFiletime,
+    DotnetTicks,
+    Cocoa,
+    Webkit,
+    Uuid,
+    Ulid,
+    Snowflake,
+    Iso8601,
+    Rfc2822,
+}
+
+impl From<Base64Variant> for Base64Format {
+    fn from(variant: Base64Variant) -> Self {
+        match variant {
+            Base64Variant::Standard => Base64Format::Standard,
+            Base64Variant::Url => Base64Format::Url,
+            Base64Variant::Mime => Base64Format::Mime,
+        }
+    }
+}
+
+impl From<Algorithm> for HashAlgorithm {
+    fn from(algorithm: Algorithm) -> Self {
+        match algorithm {
+            Algorithm::Md5 => HashAlgorithm::Md5,
+            Algorithm::Sha1 => HashAlgorithm::Sha1,
+            Algorithm::Sha224 => HashAlgorithm::Sha224,
+            Algorithm::Sha256 => HashAlgorithm::Sha256,
+            Algorithm::Sha384 => HashAlgorithm::Sha384,
+            Algorithm::Sha512 => HashAlgorithm::Sha512,
+            Algorithm::Blake3 => HashAlgorithm::Blake3,
+        }
+    }
+}
+
+impl From<Unit> for ReverseUnit {
+    fn from(unit: Unit) -> Self {
+        match unit {
+            Unit::Byte => ReverseUnit::Byte,
+            Unit::Character => ReverseUnit::Character,
+            Unit::Line => ReverseUnit::Line,
+        }
+    }
+}
+
+impl From<Side> for TrimSide {
+    fn from(side: Side) -> Self {
+        match side {
+            Side::Both => TrimSide::Both,
+            Side::Start => TrimSide::Start,
+            Side::End => TrimSide::End,
+        }
+    }
+}
+
+impl From<TimeFormat> for TimestampFormat {
+    fn from(format: TimeFormat) -> Self {
+        match format {
+            TimeFormat::UnixSeconds => TimestampFormat::UnixSeconds,
+            TimeFormat::UnixMilliseconds => TimestampFormat::UnixMilliseconds,
+            TimeFormat::UnixMicroseconds => TimestampFormat::UnixMicroseconds,
+            TimeFormat::UnixNanoseconds => TimestampFormat::UnixNanoseconds,
+            TimeFormat::WindowsFiletime => TimestampFormat::WindowsFiletime,
+            TimeFormat::DotnetTicks => TimestampFormat::DotnetTicks,
+            TimeFormat::Cocoa => TimestampFormat::Cocoa,
+            TimeFormat::Webkit => TimestampFormat::Webkit,
+            TimeFormat::Uuid => TimestampFormat::Uuid,
+            TimeFormat::Ulid => TimestampFormat::Ulid,
+            TimeFormat::Snowflake => TimestampFormat::Snowflake,
+            TimeFormat::Iso8601 => TimestampFormat::Iso8601,
+            TimeFormat::Rfc2822 => TimestampFormat::Rfc2822,
+        }
+    }
+}
+
+impl Step {
+    pub fn into_operation(self) -> Result<Box<dyn Operation>, OperationError> {
+        Ok(match self {
+            Step::Encode(encode) => match encode {
+                Encode::Base64(args) => {
+                    Box::new(Base64Encode::new(args.format.into(), !args.no_pad))
+                }
+                Encode::Base32hex => Box::new(Base32HexEncode::new()),
+                Encode::Hex(args) => {
+                    let format = if args.upper { HexFormat::Upper } else { HexFormat::Lower };
+                    Box::new(HexEncode::new(format, args.prefix, args.delimiter))
+                }
+                Encode::Html => Box::new(HtmlEncode::new()),
+                Encode::Url { non_ascii, charset } => Box::new(UrlEncode::new(non_ascii, charset)),
+                Encode::Pem { label } => Box::new(PemEncode::new(label)),
+            },
+            Step::Decode(decode) => match decode {
+                Decode::Base64(args) => {
+                    Box::new(Base64Decode::new(args.format.into(), !args.no_pad))
+                }
+                Decode::Base32hex => Box::new(Base32HexDecode::new()),
+                Decode::Hex(args) => Box::new(HexDecode::new(args.prefix, args.delimiter)),
+                Decode::Html => Box::new(HtmlDecode::new()),
+                Decode::Url => Box::new(UrlDecode::new()),
+                Decode::Pem { label } => Box::new(PemDecode::new(label)),
+                Decode::Asn1 => Box::new(Asn1Decode::new()),
+                Decode::X509 => Box::new(X509Decode::new()),
+            },
+            Step::Hash(hash) => match hash {
+                Hash::Md5 => Box::new(Md5Hash::new()),
+                Hash::Sha1 => Box::new(Sha1Hash::new()),
+                Hash::Sha224 => Box::new(Sha2Hash::new(Sha2Version::Sha224)),
+                Hash::Sha256 => Box::new(Sha2Hash::new(Sha2Version::Sha256)),
+                Hash::Sha384 => Box::new(Sha2Hash::new(Sha2Version::Sha384)),
+                Hash::Sha512 => Box::new(Sha2Hash::new(Sha2Version::Sha512)),
+                Hash::Blake3 => Box::new(Blake3Hash::new()),
+                Hash::Multi { algorithms, concatenated } => {
+                    let format = if concatenated {
+                        MultiHashFormat::Concatenated
+                    } else {
+                        MultiHashFormat::Json
+                    };
+                    let algorithms = algorithms.into_iter().map(Into::into).collect();
+                    Box::new(MultiHash::new(algorithms, format))
+                }
+                Hash::Identify { plaintext } => Box::new(IdentifyHash::new(plaintext)),
+            },
+            Step::Time(time) => Box::new(TimestampConvert::new(
+                time.from.into(),
+                time.to.into(),
+                time.timezone,
+                time.snowflake_epoch,
+            )),
+            Step::Analyze { window_size } => Box::new(Analyze::new(window_size)),
+            Step::Text(text) => match text {
+                Text::Upper => Box::new(ChangeCase::new(TextCase::Upper)),
+                Text::Lower => Box::new(ChangeCase::new(TextCase::Lower)),
+                Text::Title => Box::new(ChangeCase::new(TextCase::Title)),
+                Text::SwapCase => Box::new(ChangeCase::new(TextCase::Swap)),
+                Text::Reverse { unit } => Box::new(Reverse::new(unit.into())),
+                Text::Sort { reverse, unique } => {
+                    Box::new(SortLines::new(Some(reverse), Some(unique)))
+                }
+                Text::Unique => Box::new(UniqueLines::new()),
+                Text::Trim { side, lines } => Box::new(Trim::new(Some(side.into()), Some(lines))),
+                Text::Split { delimiter } => Box::new(Split::new(delimiter)),
+                Text::Join { delimiter } => Box::new(Join::new(delimiter)),
+                Text::Number { start } => Box::new(AddLineNumbers::new(start)),
+                Text::Unnumber => Box::new(RemoveLineNumbers::new()),
+                Text::Replace { pattern, replacement, ignore_case, first } => Box::new(
+                    RegexReplace::new(pattern, replacement, Some(ignore_case), Some(!first))?,
+                ),
+                Text::Extract { pattern, ignore_case, group, delimiter } => {
+                    Box::new(RegexExtract::new(pattern, Some(ignore_case), group, delimiter)?)
+                }
+            },
+        })
+    }
+}
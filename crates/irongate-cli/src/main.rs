@@ -0,0 +1,152 @@
+mod cli;
+
+use std::{
+    env, fs,
+    io::{self, Read, Write},
+    path::Path,
+    process::ExitCode,
+};
+
+use irongate_encore::{HexEncode, HexFormat, Operation, OperationError};
+
+fn main() -> ExitCode {
+    let (io, operations) = match cli::parse(env::args_os().skip(1)) {
+        Ok(parsed) => parsed,
+        Err(err) => err.exit(),
+    };
+    let result = read(io.input.as_deref())
+        .and_then(|input| run(&operations, input))
+        .and_then(|output| if io.hex { hex(&output) } else { Ok(output) })
+        .and_then(|output| write(io.output.as_deref(), &output));
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("irongate: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Feed the input through every operation of the chain in turn
+fn run(operations: &[Box<dyn Operation>], input: Vec<u8>) -> Result<Vec<u8>, OperationError> {
+    operations.iter().try_fold(input, |data, operation| operation.execute(&data))
+}
+
+fn hex(output: &[u8]) -> Result<Vec<u8>, OperationError> {
+    let mut hex = HexEncode::new(HexFormat::Lower, None, None).execute(output)?;
+    hex.push(b'\n');
+    Ok(hex)
+}
+
+/// The file to use, `None` for stdin or stdout
+fn file(path: Option<&Path>) -> Option<&Path> {
+    path.filter(|path| *path != Path::new("-"))
+}
+
+fn file_error(path: &Path, err: io::Error) -> OperationError {
+    OperationError::IoError(format!("{}: {err}", path.display()))
+}
+
+fn read(path: Option<&Path>) -> Result<Vec<u8>, OperationError> {
+    let mut input = vec![];
+    match file(path) {
+        Some(path) => input = fs::read(path).map_err(|err| file_error(path, err))?,
+        None => {
+            io::stdin().lock().read_to_end(&mut input)?;
+        }
+    }
+    Ok(input)
+}
+
+fn write(path: Option<&Path>, output: &[u8]) -> Result<(), OperationError> {
+    match file(path) {
+        Some(path) => fs::write(path, output).map_err(|err| file_error(path, err))?,
+        None => {
+            let mut stdout = io::stdout().lock();
+            stdout.write_all(output)?;
+            stdout.flush()?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn irongate(args: &str, input: &[u8]) -> Result<Vec<u8>, OperationError> {
+        let (io, operations) = cli::parse(args.split_whitespace()).unwrap();
+        let output = run(&operations, input.to_vec())?;
+        if io.hex {
+            hex(&output)
+        } else {
+            Ok(output)
+        }
+    }
+
+    #[test]
+    fn single_operations() {
+        assert_eq!(irongate("encode base64", b"irongate").unwrap(), b"aXJvbmdhdGU=");
+        assert_eq!(irongate("encode base64 --format url --no-pad", b"\xff\xfe").unwrap(), b"__4");
+        assert_eq!(irongate("encode hex --upper --delimiter :", b"\x01\xab").unwrap(), b"01:AB");
+        assert_eq!(irongate("decode url", b"a%20b").unwrap(), b"a b");
+        assert_eq!(
+            irongate("hash sha256 --hex", b"").unwrap(),
+            b"e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855\n"
+        );
+        assert_eq!(
+            irongate("time --from unix-seconds --timezone +01:00", b"0").unwrap(),
+            b"1970-01-01T01:00:00+01:00"
+        );
+    }
+
+    #[test]
+    fn multi_hash() {
+        assert_eq!(
+            irongate("hash multi md5 sha1", b"").unwrap(),
+            br#"{"md5":"d41d8cd98f00b204e9800998ecf8427e","sha1":"da39a3ee5e6b4b0d3255bfef95601890afd80709"}"#
+        );
+        assert_eq!(irongate("hash multi md5 blake3 --concatenated", b"").unwrap().len(), 48);
+        assert!(irongate(
+            "hash md5 + encode hex + hash identify --plaintext irongate",
+            b"irongate"
+        )
+        .unwrap()
+        .starts_with(br#"{"candidates":[{"confidence":"medium","name":"md5","verified":true}"#));
+    }
+
+    #[test]
+    fn text_operations() {
+        assert_eq!(
+            irongate("text sort --unique + text number", b"b\na\nb\n").unwrap(),
+            b"1 a\n2 b\n"
+        );
+        assert_eq!(irongate("text replace (\\w+)@ $1+ --first", b"a@b@").unwrap(), b"a+b@");
+        assert_eq!(irongate("text split , + text title", b"ab,\xffcd").unwrap(), b"Ab\n\xffCd");
+        assert_eq!(irongate("text split ++ + text join ,", b"a+b").unwrap(), b"a,b");
+        assert_eq!(irongate("text join ++", b"a\nb").unwrap(), b"a+b");
+        assert_eq!(irongate("text join +++", b"a\nb").unwrap(), b"a++b");
+    }
+
+    #[test]
+    fn chained_operations() {
+        assert_eq!(
+            irongate("decode base64 + hash md5 + encode hex", b"aXJvbmdhdGU=").unwrap(),
+            irongate("hash md5 --hex", b"irongate").unwrap().trim_ascii_end()
+        );
+        let (io, operations) = cli::parse(["--hex", "decode", "hex", "+", "hash", "sha1"]).unwrap();
+        assert!(io.hex);
+        assert_eq!(operations.len(), 2);
+    }
+
+    #[test]
+    fn errors() {
+        let err = irongate("decode base64 + encode hex", b"!!").unwrap_err();
+        assert!(err.to_string().starts_with("Decode error"));
+        assert!(cli::parse(["hash", "sha3"]).is_err());
+        assert!(cli::parse(["encode", "hex", "+"]).is_err());
+        assert!(cli::parse(Vec::<String>::new()).is_err());
+        assert!(cli::parse(["text", "replace", "(a", "b"]).is_err());
+        assert!(cli::parse(["text", "extract", "(a)", "--group", "2"]).is_err());
+    }
+}
@@ -0,0 +1,15 @@
+use std::{env, path::PathBuf};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    // The committed copy in include/ is refreshed by `just build-ffi`, builds never write to the
+    // source tree
+    let crate_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml")).unwrap();
+    cbindgen::generate_with_config(&crate_dir, config)
+        .expect("Unable to generate the C header")
+        .write_to_file(out_dir.join("irongate_encore.h"));
+}
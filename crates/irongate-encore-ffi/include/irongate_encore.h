@@ -0,0 +1,109 @@
+#ifndef IRONGATE_ENCORE_H
+#define IRONGATE_ENCORE_H
+
+/* Generated by cbindgen from crates/irongate-encore-ffi, do not edit */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Result of every fallible call, details are available from `irongate_last_error`
+ */
+typedef enum {
+  IRONGATE_STATUS_OK = 0,
+  IRONGATE_STATUS_NULL_POINTER = 1,
+  IRONGATE_STATUS_INVALID_CONFIG = 2,
+  IRONGATE_STATUS_UNKNOWN_OPERATION = 3,
+  IRONGATE_STATUS_INVALID_OPTIONS = 4,
+  IRONGATE_STATUS_DECODE_ERROR = 5,
+  IRONGATE_STATUS_ENCRYPTION_ERROR = 6,
+  IRONGATE_STATUS_IO_ERROR = 7,
+  IRONGATE_STATUS_NOT_IMPLEMENTED = 8,
+  /**
+   * The caller-provided buffer is too small, the required size is written to `output_len`
+   */
+  IRONGATE_STATUS_BUFFER_TOO_SMALL = 9,
+  IRONGATE_STATUS_PANIC = 10,
+  IRONGATE_STATUS_UNKNOWN = 11,
+} IrongateStatus;
+
+/**
+ * Opaque handle to a configured operation
+ */
+typedef struct IrongateOperation IrongateOperation;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Create an operation from a NUL-terminated JSON config and store its handle in `operation`.
+ * The handle must be released with `irongate_operation_free`.
+ *
+ * # Safety
+ * `config` must be a valid C string and `operation` a valid pointer to write the handle to.
+ */
+IrongateStatus irongate_operation_new(const char *config, IrongateOperation **operation);
+
+/**
+ * Release an operation handle, NULL is ignored.
+ *
+ * # Safety
+ * `operation` must come from `irongate_operation_new` and must not be used afterwards.
+ */
+void irongate_operation_free(IrongateOperation *operation);
+
+/**
+ * Execute the operation into a buffer allocated by the library. On success `output` and
+ * `output_len` describe the result, which must be released with `irongate_buffer_free`.
+ *
+ * # Safety
+ * `operation` must be a live handle, `input` must point to `input_len` readable bytes (or be
+ * NULL when `input_len` is 0) and `output` and `output_len` must be valid for writes.
+ */
+IrongateStatus irongate_operation_execute(const IrongateOperation *operation,
+                                          const uint8_t *input,
+                                          size_t input_len,
+                                          uint8_t **output,
+                                          size_t *output_len);
+
+/**
+ * Execute the operation into a caller-provided buffer of `capacity` bytes. The size of the
+ * result is written to `output_len`, also when `IrongateStatus::BufferTooSmall` is returned so
+ * the call can be retried with a large enough buffer.
+ * The result is built in a per-thread scratch buffer and then copied, so repeated calls do not
+ * allocate for outputs up to 1 MiB.
+ *
+ * # Safety
+ * As for `irongate_operation_execute`, with `output` pointing to `capacity` writable bytes
+ * (or NULL when `capacity` is 0).
+ */
+IrongateStatus irongate_operation_execute_into(const IrongateOperation *operation,
+                                               const uint8_t *input,
+                                               size_t input_len,
+                                               uint8_t *output,
+                                               size_t capacity,
+                                               size_t *output_len);
+
+/**
+ * Release a buffer returned by `irongate_operation_execute`, NULL is ignored.
+ *
+ * # Safety
+ * `data` and `len` must be exactly as returned by `irongate_operation_execute`.
+ */
+void irongate_buffer_free(uint8_t *data, size_t len);
+
+/**
+ * Message of the last error raised on the calling thread, or NULL. The string is owned by the
+ * library and stays valid until the next call into the library on the same thread.
+ */
+const char *irongate_last_error(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* IRONGATE_ENCORE_H */
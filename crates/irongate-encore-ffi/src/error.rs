@@ -0,0 +1,65 @@
+use std::{cell::RefCell, ffi::CString};
+
+use irongate_encore::OperationError;
+
+/// Result of every fallible call, details are available from `irongate_last_error`
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IrongateStatus {
+    Ok = 0,
+    NullPointer = 1,
+    InvalidConfig = 2,
+    UnknownOperation = 3,
+    InvalidOptions = 4,
+    DecodeError = 5,
+    EncryptionError = 6,
+    IoError = 7,
+    NotImplemented = 8,
+    /// The caller-provided buffer is too small, the required size is written to `output_len`
+    BufferTooSmall = 9,
+    Panic = 10,
+    Unknown = 11,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+pub(crate) fn set_last_error(message: String) {
+    // Messages are built from operation errors and may echo NUL bytes of the input
+    let message = CString::new(message.replace('\0', "\\0")).unwrap();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+pub(crate) fn clear_last_error() {
+    LAST_ERROR.with(|last| *last.borrow_mut() = None);
+}
+
+pub(crate) fn fail(status: IrongateStatus, message: impl Into<String>) -> IrongateStatus {
+    set_last_error(message.into());
+    status
+}
+
+impl From<OperationError> for IrongateStatus {
+    fn from(err: OperationError) -> Self {
+        let status = match err {
+            OperationError::DecodeUtf8Error | OperationError::DecodeError { .. } => {
+                IrongateStatus::DecodeError
+            }
+            OperationError::EncryptionError(_) => IrongateStatus::EncryptionError,
+            OperationError::IoError(_) => IrongateStatus::IoError,
+            OperationError::InvalidOptions(_) => IrongateStatus::InvalidOptions,
+            OperationError::UnknownOperation(_) => IrongateStatus::UnknownOperation,
+            OperationError::NotImplemented(_) => IrongateStatus::NotImplemented,
+            OperationError::Unknown => IrongateStatus::Unknown,
+        };
+        fail(status, err.to_string())
+    }
+}
+
+/// Message of the last error raised on the calling thread, or NULL. The string is owned by the
+/// library and stays valid until the next call into the library on the same thread.
+#[no_mangle]
+pub extern "C" fn irongate_last_error() -> *const std::ffi::c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(std::ptr::null(), |last| last.as_ptr()))
+}
@@ -0,0 +1,335 @@
+//! C ABI over the irongate-encore operation registry. Operations are created from a JSON
+//! config such as `{"operation": "hex_encode", "options": {"format": "Upper"}}` and are safe
+//! to share between threads once created. cbindgen generates the header into `OUT_DIR` during
+//! the build, and `just build-ffi` copies it to the committed `include/irongate_encore.h`.
+
+mod error;
+
+use std::{
+    cell::RefCell,
+    ffi::{c_char, CStr},
+    panic::{self, AssertUnwindSafe},
+    ptr, slice,
+};
+
+use error::{clear_last_error, fail};
+pub use error::{irongate_last_error, IrongateStatus};
+use irongate_encore::registry::{self, BoxedOperation};
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Opaque handle to a configured operation
+pub struct IrongateOperation {
+    operation: BoxedOperation,
+}
+
+#[derive(Deserialize)]
+struct Config {
+    operation: String,
+    #[serde(default)]
+    options: Value,
+}
+
+/// Largest scratch buffer kept between calls to `irongate_operation_execute_into`
+const SCRATCH_LIMIT: usize = 1 << 20;
+
+thread_local! {
+    static SCRATCH: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Run `body` with the last error cleared, turning panics into `IrongateStatus::Panic`
+fn guard(body: impl FnOnce() -> IrongateStatus) -> IrongateStatus {
+    clear_last_error();
+    panic::catch_unwind(AssertUnwindSafe(body))
+        .unwrap_or_else(|_| fail(IrongateStatus::Panic, "irongate-encore panicked"))
+}
+
+/// # Safety
+/// `data` must be NULL with `len` 0, or point to `len` readable bytes
+unsafe fn input<'a>(data: *const u8, len: usize) -> Option<&'a [u8]> {
+    match (data.is_null(), len) {
+        (true, 0) => Some(&[]),
+        (true, _) => None,
+        (false, _) => Some(slice::from_raw_parts(data, len)),
+    }
+}
+
+/// Create an operation from a NUL-terminated JSON config and store its handle in `operation`.
+/// The handle must be released with `irongate_operation_free`.
+///
+/// # Safety
+/// `config` must be a valid C string and `operation` a valid pointer to write the handle to.
+#[no_mangle]
+pub unsafe extern "C" fn irongate_operation_new(
+    config: *const c_char,
+    operation: *mut *mut IrongateOperation,
+) -> IrongateStatus {
+    guard(|| {
+        if config.is_null() || operation.is_null() {
+            return fail(IrongateStatus::NullPointer, "config and operation must not be NULL");
+        }
+        let config = match serde_json::from_slice::<Config>(CStr::from_ptr(config).to_bytes()) {
+            Ok(config) => config,
+            Err(err) => {
+                return fail(IrongateStatus::InvalidConfig, format!("Invalid config: {err}"))
+            }
+        };
+        match registry::create(&config.operation, config.options) {
+            Ok(created) => {
+                *operation = Box::into_raw(Box::new(IrongateOperation { operation: created }));
+                IrongateStatus::Ok
+            }
+            Err(err) => err.into(),
+        }
+    })
+}
+
+/// Release an operation handle, NULL is ignored.
+///
+/// # Safety
+/// `operation` must come from `irongate_operation_new` and must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn irongate_operation_free(operation: *mut IrongateOperation) {
+    if !operation.is_null() {
+        drop(Box::from_raw(operation));
+    }
+}
+
+/// Execute the operation into a buffer allocated by the library. On success `output` and
+/// `output_len` describe the result, which must be released with `irongate_buffer_free`.
+///
+/// # Safety
+/// `operation` must be a live handle, `input` must point to `input_len` readable bytes (or be
+/// NULL when `input_len` is 0) and `output` and `output_len` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn irongate_operation_execute(
+    operation: *const IrongateOperation,
+    input: *const u8,
+    input_len: usize,
+    output: *mut *mut u8,
+    output_len: *mut usize,
+) -> IrongateStatus {
+    guard(|| {
+        let (Some(operation), Some(input)) = (operation.as_ref(), self::input(input, input_len))
+        else {
+            return fail(IrongateStatus::NullPointer, "operation and input must not be NULL");
+        };
+        if output.is_null() || output_len.is_null() {
+            return fail(IrongateStatus::NullPointer, "output and output_len must not be NULL");
+        }
+        match operation.operation.execute(input) {
+            Ok(result) => {
+                let result = Box::into_raw(result.into_boxed_slice());
+                *output_len = result.len();
+                *output = result.cast();
+                IrongateStatus::Ok
+            }
+            Err(err) => err.into(),
+        }
+    })
+}
+
+/// Execute the operation into a caller-provided buffer of `capacity` bytes. The size of the
+/// result is written to `output_len`, also when `IrongateStatus::BufferTooSmall` is returned so
+/// the call can be retried with a large enough buffer.
+/// The result is built in a per-thread scratch buffer and then copied, so repeated calls do not
+/// allocate for outputs up to 1 MiB.
+///
+/// # Safety
+/// As for `irongate_operation_execute`, with `output` pointing to `capacity` writable bytes
+/// (or NULL when `capacity` is 0).
+#[no_mangle]
+pub unsafe extern "C" fn irongate_operation_execute_into(
+    operation: *const IrongateOperation,
+    input: *const u8,
+    input_len: usize,
+    output: *mut u8,
+    capacity: usize,
+    output_len: *mut usize,
+) -> IrongateStatus {
+    guard(|| {
+        let (Some(operation), Some(input)) = (operation.as_ref(), self::input(input, input_len))
+        else {
+            return fail(IrongateStatus::NullPointer, "operation and input must not be NULL");
+        };
+        if (output.is_null() && capacity != 0) || output_len.is_null() {
+            return fail(IrongateStatus::NullPointer, "output and output_len must not be NULL");
+        }
+        SCRATCH.with(|scratch| {
+            let mut result = scratch.borrow_mut();
+            result.clear();
+            let status = match operation.operation.execute_into(input, &mut result) {
+                Err(err) => err.into(),
+                Ok(()) if result.len() > capacity => {
+                    *output_len = result.len();
+                    fail(
+                        IrongateStatus::BufferTooSmall,
+                        format!("Output needs {} bytes, buffer holds {capacity}", result.len()),
+                    )
+                }
+                Ok(()) => {
+                    *output_len = result.len();
+                    ptr::copy_nonoverlapping(result.as_ptr(), output, result.len());
+                    IrongateStatus::Ok
+                }
+            };
+            if result.capacity() > SCRATCH_LIMIT {
+                *result = Vec::new();
+            }
+            status
+        })
+    })
+}
+
+/// Release a buffer returned by `irongate_operation_execute`, NULL is ignored.
+///
+/// # Safety
+/// `data` and `len` must be exactly as returned by `irongate_operation_execute`.
+#[no_mangle]
+pub unsafe extern "C" fn irongate_buffer_free(data: *mut u8, len: usize) {
+    if !data.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(data, len)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CString;
+
+    use super::*;
+
+    #[test]
+    fn committed_header_is_up_to_date() {
+        let generated = include_str!(concat!(env!("OUT_DIR"), "/irongate_encore.h"));
+        let committed = include_str!("../include/irongate_encore.h");
+        assert!(
+            committed == generated,
+            "run `just build-ffi` to refresh include/irongate_encore.h"
+        );
+    }
+
+    fn create(config: &str) -> Result<*mut IrongateOperation, IrongateStatus> {
+        let config = CString::new(config).unwrap();
+        let mut operation = ptr::null_mut();
+        match unsafe { irongate_operation_new(config.as_ptr(), &mut operation) } {
+            IrongateStatus::Ok => Ok(operation),
+            status => Err(status),
+        }
+    }
+
+    fn last_error() -> String {
+        unsafe { CStr::from_ptr(irongate_last_error()) }.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn execute_allocates_output() {
+        let operation =
+            create(r#"{"operation": "hex_encode", "options": {"format": "Upper"}}"#).unwrap();
+        let (mut output, mut output_len) = (ptr::null_mut(), 0);
+        let status = unsafe {
+            irongate_operation_execute(
+                operation,
+                b"\x01\xab".as_ptr(),
+                2,
+                &mut output,
+                &mut output_len,
+            )
+        };
+        assert_eq!(status, IrongateStatus::Ok);
+        assert_eq!(unsafe { slice::from_raw_parts(output, output_len) }, b"01AB");
+        assert!(irongate_last_error().is_null());
+        unsafe {
+            irongate_buffer_free(output, output_len);
+            irongate_operation_free(operation);
+        }
+    }
+
+    #[test]
+    fn execute_into_caller_buffer() {
+        let operation = create(r#"{"operation": "md5_hash"}"#).unwrap();
+        let mut buffer = [0; 16];
+        let mut output_len = 0;
+        let status = unsafe {
+            irongate_operation_execute_into(
+                operation,
+                ptr::null(),
+                0,
+                buffer.as_mut_ptr(),
+                8,
+                &mut output_len,
+            )
+        };
+        assert_eq!(status, IrongateStatus::BufferTooSmall);
+        assert_eq!(output_len, 16);
+        let status = unsafe {
+            irongate_operation_execute_into(
+                operation,
+                ptr::null(),
+                0,
+                buffer.as_mut_ptr(),
+                16,
+                &mut output_len,
+            )
+        };
+        assert_eq!(status, IrongateStatus::Ok);
+        assert_eq!(buffer[..2], [0xd4, 0x1d]);
+        unsafe { irongate_operation_free(operation) };
+    }
+
+    #[test]
+    fn execute_into_reuses_scratch() {
+        let operation =
+            create(r#"{"operation": "hex_encode", "options": {"format": "Lower"}}"#).unwrap();
+        let mut buffer = vec![0; 2 * SCRATCH_LIMIT];
+        let mut output_len = 0;
+        let large = vec![0xab; SCRATCH_LIMIT];
+        let expected = "ab".repeat(SCRATCH_LIMIT);
+        for (input, expected) in [(&large[..], &expected[..]), (b"ab", "6162"), (b"", "")] {
+            let status = unsafe {
+                irongate_operation_execute_into(
+                    operation,
+                    input.as_ptr(),
+                    input.len(),
+                    buffer.as_mut_ptr(),
+                    buffer.len(),
+                    &mut output_len,
+                )
+            };
+            assert_eq!(status, IrongateStatus::Ok);
+            assert_eq!(buffer[..output_len], *expected.as_bytes());
+        }
+        unsafe { irongate_operation_free(operation) };
+    }
+
+    #[test]
+    fn errors_set_last_error() {
+        assert_eq!(create("{").unwrap_err(), IrongateStatus::InvalidConfig);
+        assert!(last_error().starts_with("Invalid config"));
+        assert_eq!(
+            create(r#"{"operation": "rot13"}"#).unwrap_err(),
+            IrongateStatus::UnknownOperation
+        );
+        assert_eq!(last_error(), "Unknown operation: rot13");
+        assert_eq!(
+            create(r#"{"operation": "sha2_hash", "options": {"version": "Sha1"}}"#).unwrap_err(),
+            IrongateStatus::InvalidOptions
+        );
+
+        let operation = create(r#"{"operation": "hex_decode"}"#).unwrap();
+        let (mut output, mut output_len) = (ptr::null_mut(), 0);
+        let status = unsafe {
+            irongate_operation_execute(operation, b"zz".as_ptr(), 2, &mut output, &mut output_len)
+        };
+        assert_eq!(status, IrongateStatus::DecodeError);
+        assert_eq!(last_error(), "Decode error: Invalid Hex input");
+        let status = unsafe {
+            irongate_operation_execute(operation, ptr::null(), 1, &mut output, &mut output_len)
+        };
+        assert_eq!(status, IrongateStatus::NullPointer);
+        unsafe { irongate_operation_free(operation) };
+        assert_eq!(
+            unsafe { irongate_operation_new(ptr::null(), &mut ptr::null_mut()) },
+            IrongateStatus::NullPointer
+        );
+    }
+}
@@ -0,0 +1,31 @@
+use pyo3::{create_exception, exceptions::PyException, prelude::*};
+
+create_exception!(irongate_encore, OperationError, PyException, "Base class of all errors");
+create_exception!(irongate_encore, DecodeError, OperationError, "Malformed input");
+create_exception!(irongate_encore, EncryptionError, OperationError);
+create_exception!(irongate_encore, InvalidOptionsError, OperationError);
+create_exception!(irongate_encore, UnknownOperationError, OperationError);
+
+pub(crate) fn to_py_err(err: irongate_encore::OperationError) -> PyErr {
+    let message = err.to_string();
+    match err {
+        irongate_encore::OperationError::DecodeUtf8Error
+        | irongate_encore::OperationError::DecodeError { .. } => DecodeError::new_err(message),
+        irongate_encore::OperationError::EncryptionError(_) => EncryptionError::new_err(message),
+        irongate_encore::OperationError::InvalidOptions(_) => InvalidOptionsError::new_err(message),
+        irongate_encore::OperationError::UnknownOperation(_) => {
+            UnknownOperationError::new_err(message)
+        }
+        _ => OperationError::new_err(message),
+    }
+}
+
+pub(crate) fn register(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = module.py();
+    module.add("OperationError", py.get_type::<OperationError>())?;
+    module.add("DecodeError", py.get_type::<DecodeError>())?;
+    module.add("EncryptionError", py.get_type::<EncryptionError>())?;
+    module.add("InvalidOptionsError", py.get_type::<InvalidOptionsError>())?;
+    module.add("UnknownOperationError", py.get_type::<UnknownOperationError>())?;
+    Ok(())
+}
@@ -0,0 +1,160 @@
+//! Python bindings exposing every irongate-encore operation as a class with an `apply` method.
+//! Options are passed as keyword arguments using the names of the registry options schema.
+
+mod errors;
+mod options;
+
+use irongate_encore::{registry::BoxedOperation, OperationInfo};
+use pyo3::{
+    prelude::*,
+    types::{PyBytes, PyDict},
+};
+
+pub use errors::{
+    DecodeError, EncryptionError, InvalidOptionsError, OperationError, UnknownOperationError,
+};
+
+/// Inputs from this size on are processed without holding the GIL so other threads can run
+const RELEASE_GIL_THRESHOLD: usize = 64 * 1024;
+
+fn build<T: OperationInfo>(options: Option<&Bound<'_, PyDict>>) -> PyResult<BoxedOperation> {
+    irongate_encore::registry::create(T::ID, options::to_json(options)?).map_err(errors::to_py_err)
+}
+
+fn apply<'py>(
+    py: Python<'py>,
+    operation: &(dyn irongate_encore::Operation + Send + Sync),
+    input: &[u8],
+) -> PyResult<Bound<'py, PyBytes>> {
+    let output = if input.len() >= RELEASE_GIL_THRESHOLD {
+        py.allow_threads(|| operation.execute(input))
+    } else {
+        operation.execute(input)
+    };
+    Ok(PyBytes::new(py, &output.map_err(errors::to_py_err)?))
+}
+
+macro_rules! operations {
+    ($($name:ident),* $(,)?) => {
+        $(
+            #[pyclass(frozen, module = "irongate_encore")]
+            pub struct $name {
+                operation: BoxedOperation,
+            }
+
+            #[pymethods]
+            impl $name {
+                #[new]
+                #[pyo3(signature = (**options))]
+                fn new(options: Option<&Bound<'_, PyDict>>) -> PyResult<Self> {
+                    Ok($name { operation: build::<irongate_encore::$name>(options)? })
+                }
+
+                fn apply<'py>(&self, py: Python<'py>, input: &[u8]) -> PyResult<Bound<'py, PyBytes>> {
+                    apply(py, self.operation.as_ref(), input)
+                }
+            }
+        )*
+
+        fn add_operations(module: &Bound<'_, PyModule>) -> PyResult<()> {
+            $(module.add_class::<$name>()?;)*
+            Ok(())
+        }
+    };
+}
+
+operations!(
+    Base32HexEncode,
+    Base32HexDecode,
+    Base64Encode,
+    Base64Decode,
+    HexEncode,
+    HexDecode,
+    HtmlEncode,
+    HtmlDecode,
+    PemEncode,
+    PemDecode,
+    UrlEncode,
+    UrlDecode,
+    Md5Hash,
+    Sha1Hash,
+    Sha2Hash,
+    Blake3Hash,
+    MultiHash,
+    IdentifyHash,
+    Asn1Decode,
+    X509Decode,
+    TimestampConvert,
+    Analyze,
+    RegexReplace,
+    RegexExtract,
+    ChangeCase,
+    Reverse,
+    SortLines,
+    UniqueLines,
+    Trim,
+    Split,
+    Join,
+    AddLineNumbers,
+    RemoveLineNumbers,
+);
+
+#[pymodule]
+#[pyo3(name = "irongate_encore")]
+fn irongate_encore_py(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    errors::register(module)?;
+    add_operations(module)
+}
+
+#[cfg(test)]
+mod tests {
+    use pyo3::{py_run, wrap_pymodule};
+
+    use super::*;
+
+    // abi3-py38 modules can only be initialised once per process, so one test drives the module
+    #[test]
+    fn module_operations() {
+        Python::with_gil(|py| {
+            let module = wrap_pymodule!(irongate_encore_py)(py);
+            py_run!(
+                py,
+                module,
+                r#"
+assert module.HexEncode(format="Upper", delimiter=":").apply(b"\x01\xab") == b"01:AB"
+assert module.Base64Decode(format="standard", pad=True).apply(b"aXJvbmdhdGU=") == b"irongate"
+assert module.Sha2Hash(version="Sha256").apply(b"").hex().startswith("e3b0c442")
+assert module.Md5Hash().apply(b"").hex() == "d41d8cd98f00b204e9800998ecf8427e"
+
+try:
+    module.HexDecode().apply(b"zz")
+    raise AssertionError("expected DecodeError")
+except module.DecodeError as err:
+    assert isinstance(err, module.OperationError)
+
+try:
+    module.Sha2Hash(version="Sha1")
+    raise AssertionError("expected InvalidOptionsError")
+except module.InvalidOptionsError as err:
+    assert "sha2_hash" in str(err)
+
+try:
+    module.Base64Encode(format=b"standard")
+    raise AssertionError("expected TypeError")
+except TypeError:
+    pass
+"#
+            );
+        });
+    }
+
+    #[test]
+    fn apply_releases_gil_for_large_inputs() {
+        Python::with_gil(|py| {
+            let decode = irongate_encore::registry::create("hex_decode", serde_json::Value::Null);
+            let encoded = b"61".repeat(RELEASE_GIL_THRESHOLD);
+            let output = apply(py, decode.unwrap().as_ref(), &encoded).unwrap();
+            assert_eq!(output.as_bytes(), vec![b'a'; RELEASE_GIL_THRESHOLD]);
+        });
+    }
+}
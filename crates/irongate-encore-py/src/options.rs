@@ -0,0 +1,113 @@
+use pyo3::{
+    exceptions::PyTypeError,
+    prelude::*,
+    types::{PyBool, PyDict, PyFloat, PyInt, PyList, PyString, PyTuple},
+};
+use serde_json::{Map, Number, Value};
+
+/// Keyword arguments as the JSON options understood by the registry. A trailing underscore is
+/// dropped from names so that options such as `from` can be passed as `from_`.
+pub(crate) fn to_json(options: Option<&Bound<'_, PyDict>>) -> PyResult<Value> {
+    match options {
+        Some(options) => kwargs_to_json(options),
+        None => Ok(Value::Null),
+    }
+}
+
+fn kwargs_to_json(kwargs: &Bound<'_, PyDict>) -> PyResult<Value> {
+    let object = dict_to_map(kwargs)?
+        .into_iter()
+        .map(|(name, value)| match name.strip_suffix('_') {
+            Some(name) => (name.to_string(), value),
+            None => (name, value),
+        })
+        .collect();
+    Ok(Value::Object(object))
+}
+
+fn dict_to_map(dict: &Bound<'_, PyDict>) -> PyResult<Map<String, Value>> {
+    let mut object = Map::new();
+    for (key, value) in dict {
+        let key = key
+            .downcast::<PyString>()
+            .map_err(|_| PyTypeError::new_err("option names must be strings"))?;
+        object.insert(key.to_cow()?.into_owned(), value_to_json(&value)?);
+    }
+    Ok(object)
+}
+
+fn value_to_json(value: &Bound<'_, PyAny>) -> PyResult<Value> {
+    if value.is_none() {
+        return Ok(Value::Null);
+    }
+    // bool is a subclass of int and has to be checked first
+    if let Ok(value) = value.downcast::<PyBool>() {
+        return Ok(Value::Bool(value.is_true()));
+    }
+    if value.is_instance_of::<PyInt>() {
+        return match value.extract::<i64>() {
+            Ok(int) => Ok(Value::from(int)),
+            Err(_) => Ok(Value::from(value.extract::<u64>()?)),
+        };
+    }
+    if let Ok(value) = value.downcast::<PyFloat>() {
+        return Number::from_f64(value.value())
+            .map(Value::Number)
+            .ok_or_else(|| PyTypeError::new_err("options must be finite numbers"));
+    }
+    if let Ok(value) = value.downcast::<PyString>() {
+        return Ok(Value::String(value.to_cow()?.into_owned()));
+    }
+    if let Ok(value) = value.downcast::<PyDict>() {
+        return dict_to_map(value).map(Value::Object);
+    }
+    if value.is_instance_of::<PyList>() || value.is_instance_of::<PyTuple>() {
+        return value.try_iter()?.map(|item| value_to_json(&item?)).collect();
+    }
+    Err(PyTypeError::new_err(format!("unsupported option type: {}", value.get_type().qualname()?)))
+}
+
+#[cfg(test)]
+mod tests {
+    use pyo3::types::PyBytes;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn to_json_converts_kwargs() {
+        Python::with_gil(|py| {
+            let options = PyDict::new(py);
+            options.set_item("format", "Upper").unwrap();
+            options.set_item("pad", true).unwrap();
+            options.set_item("size", u64::MAX).unwrap();
+            options.set_item("ratio", 0.5).unwrap();
+            options.set_item("prefix", py.None()).unwrap();
+            options.set_item("items", PyList::new(py, [1, 2]).unwrap()).unwrap();
+            options.set_item("from_", "unix_seconds").unwrap();
+            let expected = json!({
+                "format": "Upper",
+                "pad": true,
+                "size": u64::MAX,
+                "ratio": 0.5,
+                "prefix": null,
+                "items": [1, 2],
+                "from": "unix_seconds"
+            });
+            assert_eq!(to_json(Some(&options)).unwrap(), expected);
+            assert_eq!(to_json(None).unwrap(), Value::Null);
+        });
+    }
+
+    #[test]
+    fn to_json_rejects_unsupported_types() {
+        Python::with_gil(|py| {
+            let options = PyDict::new(py);
+            options.set_item("data", PyBytes::new(py, b"bytes")).unwrap();
+            assert!(to_json(Some(&options)).unwrap_err().is_instance_of::<PyTypeError>(py));
+            let options = PyDict::new(py);
+            options.set_item("ratio", f64::NAN).unwrap();
+            assert!(to_json(Some(&options)).is_err());
+        });
+    }
+}
@@ -0,0 +1,73 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use irongate_encore::{
+    Base64Decode, Base64Encode, Base64Format, HexDecode, HexEncode, HexFormat, Md5Hash, Operation,
+    Sha2Hash, Sha2Version, UrlEncode,
+};
+
+const SMALL_VALUES: usize = 10_000;
+
+fn small_values() -> Vec<Vec<u8>> {
+    (0..SMALL_VALUES).map(|i| format!("irongate-{i}@example.com").into_bytes()).collect()
+}
+
+fn bench_operation(c: &mut Criterion, name: &str, operation: &dyn Operation, inputs: &[Vec<u8>]) {
+    let mut group = c.benchmark_group(name);
+    group.bench_function("execute", |b| {
+        b.iter(|| {
+            for input in inputs {
+                black_box(operation.execute(black_box(input)).unwrap());
+            }
+        })
+    });
+    group.bench_function("execute_into", |b| {
+        b.iter_batched_ref(
+            || Vec::with_capacity(1024),
+            |out| {
+                for input in inputs {
+                    out.clear();
+                    operation.execute_into(black_box(input), out).unwrap();
+                    black_box(&out);
+                }
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+fn encoded_with(operation: &dyn Operation, inputs: &[Vec<u8>]) -> Vec<Vec<u8>> {
+    inputs.iter().map(|input| operation.execute(input).unwrap()).collect()
+}
+
+fn operations(c: &mut Criterion) {
+    let inputs = small_values();
+
+    let hex_encode = HexEncode::new(HexFormat::Lower, None, None);
+    bench_operation(c, "hex_encode", &hex_encode, &inputs);
+    let hex_encode_prefixed = HexEncode::new(HexFormat::Upper, Some("\\x".to_string()), None);
+    bench_operation(c, "hex_encode_prefixed", &hex_encode_prefixed, &inputs);
+
+    let hex_decode = HexDecode::new(None, None);
+    bench_operation(c, "hex_decode", &hex_decode, &encoded_with(&hex_encode, &inputs));
+    let hex_decode = HexDecode::new(Some("\\x".to_string()), None);
+    bench_operation(
+        c,
+        "hex_decode_prefixed",
+        &hex_decode,
+        &encoded_with(&hex_encode_prefixed, &inputs),
+    );
+
+    let base64_encode = Base64Encode::new(Base64Format::Standard, true);
+    bench_operation(c, "base64_encode", &base64_encode, &inputs);
+    let base64_decode = Base64Decode::new(Base64Format::Standard, true);
+    bench_operation(c, "base64_decode", &base64_decode, &encoded_with(&base64_encode, &inputs));
+
+    let url_encode = UrlEncode::new(true, Some("@.-".to_string()));
+    bench_operation(c, "url_encode", &url_encode, &inputs);
+
+    bench_operation(c, "md5_hash", &Md5Hash::new(), &inputs);
+    bench_operation(c, "sha256_hash", &Sha2Hash::new(Sha2Version::Sha256), &inputs);
+}
+
+criterion_group!(benches, operations);
+criterion_main!(benches);
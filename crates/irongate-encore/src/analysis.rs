@@ -0,0 +1,290 @@
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::Operation;
+use crate::OperationError;
+#[cfg(feature = "registry")]
+use crate::{Category, OperationInfo};
+
+const DEFAULT_WINDOW_SIZE: usize = 256;
+
+struct Magic {
+    offset: usize,
+    bytes: &'static [u8],
+    name: &'static str,
+    mime: &'static str,
+}
+
+const fn magic(
+    offset: usize,
+    bytes: &'static [u8],
+    name: &'static str,
+    mime: &'static str,
+) -> Magic {
+    Magic { offset, bytes, name, mime }
+}
+
+/// Longer signatures come first so that they win over shorter prefixes
+const MAGICS: &[Magic] = &[
+    magic(0, b"SQLite format 3\0", "sqlite", "application/vnd.sqlite3"),
+    magic(0, b"\x89PNG\r\n\x1a\n", "png", "image/png"),
+    magic(0, b"7z\xbc\xaf\x27\x1c", "7z", "application/x-7z-compressed"),
+    magic(0, b"\xfd7zXZ\0", "xz", "application/x-xz"),
+    magic(0, b"Rar!\x1a\x07", "rar", "application/vnd.rar"),
+    magic(0, b"GIF87a", "gif", "image/gif"),
+    magic(0, b"GIF89a", "gif", "image/gif"),
+    magic(0, b"-----BEGIN ", "pem", "application/x-pem-file"),
+    magic(0, b"%PDF-", "pdf", "application/pdf"),
+    magic(257, b"ustar", "tar", "application/x-tar"),
+    magic(8, b"WEBP", "webp", "image/webp"),
+    magic(8, b"WAVE", "wav", "audio/wav"),
+    magic(0, b"PK\x03\x04", "zip", "application/zip"),
+    magic(0, b"PK\x05\x06", "zip", "application/zip"),
+    magic(0, b"\x28\xb5\x2f\xfd", "zstd", "application/zstd"),
+    magic(0, b"\x7fELF", "elf", "application/x-elf"),
+    magic(0, b"\0asm", "wasm", "application/wasm"),
+    magic(0, b"\xfe\xed\xfa\xce", "mach-o", "application/x-mach-binary"),
+    magic(0, b"\xfe\xed\xfa\xcf", "mach-o", "application/x-mach-binary"),
+    magic(0, b"\xce\xfa\xed\xfe", "mach-o", "application/x-mach-binary"),
+    magic(0, b"\xcf\xfa\xed\xfe", "mach-o", "application/x-mach-binary"),
+    magic(0, b"OggS", "ogg", "application/ogg"),
+    magic(0, b"fLaC", "flac", "audio/flac"),
+    magic(0, b"\xff\xd8\xff", "jpeg", "image/jpeg"),
+    magic(0, b"BZh", "bzip2", "application/x-bzip2"),
+    magic(0, b"ID3", "mp3", "audio/mpeg"),
+    magic(0, b"\x1f\x8b", "gzip", "application/gzip"),
+    magic(0, b"\x78\x01", "zlib", "application/zlib"),
+    magic(0, b"\x78\x5e", "zlib", "application/zlib"),
+    magic(0, b"\x78\x9c", "zlib", "application/zlib"),
+    magic(0, b"\x78\xda", "zlib", "application/zlib"),
+    magic(0, b"MZ", "pe", "application/vnd.microsoft.portable-executable"),
+];
+
+/// Byte statistics as JSON: Shannon entropy in bits per byte, the byte histogram, the share
+/// of printable, ASCII and valid UTF-8 bytes, the file type guessed from magic bytes and the
+/// entropy of consecutive windows, where compressed or encrypted regions stand out near 8.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Analyze {
+    window_size: Option<usize>,
+}
+
+impl Operation for Analyze {
+    fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
+        let mut output = vec![];
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        let window_size = self.window_size.unwrap_or(DEFAULT_WINDOW_SIZE);
+        if window_size == 0 {
+            return Err(OperationError::InvalidOptions("window_size must not be 0".into()));
+        }
+
+        let counts = histogram(input);
+        let windows: Vec<Value> = input
+            .chunks(window_size)
+            .enumerate()
+            .map(|(index, window)| {
+                json!({ "offset": index * window_size, "entropy": round(entropy(&histogram(window), window.len())) })
+            })
+            .collect();
+        let printable =
+            input.iter().filter(|byte| matches!(byte, 0x20..=0x7e | b'\t' | b'\n' | b'\r')).count();
+        let ascii = input.iter().filter(|byte| byte.is_ascii()).count();
+        let utf8 = valid_utf8_len(input);
+
+        let analysis = json!({
+            "size": input.len(),
+            "entropy": round(entropy(&counts, input.len())),
+            "unique_bytes": counts.iter().filter(|count| **count != 0).count(),
+            "printable_ratio": ratio(printable, input.len()),
+            "ascii_ratio": ratio(ascii, input.len()),
+            "utf8_ratio": ratio(utf8, input.len()),
+            "utf8_valid": utf8 == input.len(),
+            "file_type": file_type(input),
+            "histogram": counts.as_slice(),
+            "window_size": window_size,
+            "windows": windows,
+        });
+        out.extend_from_slice(analysis.to_string().as_bytes());
+        Ok(())
+    }
+}
+
+impl Analyze {
+    pub const fn new(window_size: Option<usize>) -> Self {
+        Analyze { window_size }
+    }
+}
+
+fn histogram(input: &[u8]) -> [usize; 256] {
+    let mut histogram = [0; 256];
+    for byte in input {
+        histogram[*byte as usize] += 1;
+    }
+    histogram
+}
+
+fn entropy(histogram: &[usize; 256], len: usize) -> f64 {
+    let len = len as f64;
+    histogram
+        .iter()
+        .filter(|count| **count != 0)
+        .map(|count| {
+            let probability = *count as f64 / len;
+            -probability * probability.log2()
+        })
+        .sum()
+}
+
+fn ratio(count: usize, len: usize) -> f64 {
+    if len == 0 {
+        0.0
+    } else {
+        round(count as f64 / len as f64)
+    }
+}
+
+fn round(value: f64) -> f64 {
+    (value * 10_000.0).round() / 10_000.0
+}
+
+/// Number of bytes belonging to well-formed UTF-8 sequences
+fn valid_utf8_len(mut input: &[u8]) -> usize {
+    let mut valid = 0;
+    loop {
+        match core::str::from_utf8(input) {
+            Ok(_) => return valid + input.len(),
+            Err(error) => {
+                valid += error.valid_up_to();
+                // A truncated sequence at the end has no error length
+                let skip = error.valid_up_to() + error.error_len().unwrap_or(input.len());
+                input = input.get(skip..).unwrap_or_default();
+            }
+        }
+    }
+}
+
+fn file_type(input: &[u8]) -> Value {
+    let matches = |magic: &&Magic| {
+        input.get(magic.offset..magic.offset + magic.bytes.len()) == Some(magic.bytes)
+            && (magic.offset != 8 || input.starts_with(b"RIFF"))
+    };
+    match MAGICS.iter().find(matches) {
+        Some(magic) => json!({ "name": magic.name, "mime": magic.mime }),
+        None => Value::Null,
+    }
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for Analyze {
+    const ID: &'static str = "analyze";
+    const NAME: &'static str = "Analyze";
+    const CATEGORY: Category = Category::Analysis;
+    const DESCRIPTION: &'static str =
+        "Entropy, byte histogram, text ratios and file type detection as JSON";
+
+    fn options_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "window_size": {
+                    "type": ["integer", "null"],
+                    "minimum": 1,
+                    "default": DEFAULT_WINDOW_SIZE
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analyze(window_size: Option<usize>, input: &[u8]) -> Value {
+        let output = Analyze::new(window_size).execute(input).unwrap();
+        serde_json::from_slice(&output).unwrap()
+    }
+
+    #[test]
+    fn analyze_text() {
+        let analysis = analyze(None, "héllo\n".as_bytes());
+        assert_eq!(analysis["size"], 7);
+        assert_eq!(analysis["unique_bytes"], 6);
+        assert_eq!(analysis["entropy"], 2.5216);
+        assert_eq!(analysis["printable_ratio"], 0.7143);
+        assert_eq!(analysis["ascii_ratio"], 0.7143);
+        assert_eq!(analysis["utf8_ratio"], 1.0);
+        assert_eq!(analysis["utf8_valid"], true);
+        assert_eq!(analysis["file_type"], Value::Null);
+        assert_eq!(analysis["histogram"][b'l' as usize], 2);
+        assert_eq!(analysis["windows"], json!([{ "offset": 0, "entropy": 2.5216 }]));
+    }
+
+    #[test]
+    fn analyze_uniform() {
+        let input: Vec<u8> = (0..=255).cycle().take(1024).collect();
+        let analysis = analyze(Some(512), &input);
+        assert_eq!(analysis["entropy"], 8.0);
+        assert_eq!(analysis["unique_bytes"], 256);
+        assert_eq!(analysis["utf8_valid"], false);
+        assert_eq!(analysis["utf8_ratio"], 0.5);
+        assert_eq!(
+            analysis["windows"],
+            json!([{ "offset": 0, "entropy": 8.0 }, { "offset": 512, "entropy": 8.0 }])
+        );
+    }
+
+    #[test]
+    fn analyze_windows_locate_regions() {
+        let mut input = vec![b'A'; 64];
+        input.extend(0..=63);
+        input.push(b'A');
+        let analysis = analyze(Some(64), &input);
+        assert_eq!(
+            analysis["windows"],
+            json!([
+                { "offset": 0, "entropy": 0.0 },
+                { "offset": 64, "entropy": 6.0 },
+                { "offset": 128, "entropy": 0.0 }
+            ])
+        );
+    }
+
+    #[test]
+    fn analyze_file_type() {
+        let file_type = |input: &[u8]| analyze(None, input)["file_type"]["name"].clone();
+        assert_eq!(file_type(b"\x1f\x8b\x08\x00"), "gzip");
+        assert_eq!(file_type(b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR"), "png");
+        assert_eq!(file_type(b"RIFF\x24\0\0\0WAVEfmt "), "wav");
+        assert_eq!(file_type(b"\x28\xb5\x2f\xfd\x20"), "zstd");
+        let mut tar = vec![0; 512];
+        tar[257..262].copy_from_slice(b"ustar");
+        assert_eq!(file_type(&tar), "tar");
+        assert_eq!(file_type(b"MZ\x90\0"), "pe");
+        assert_eq!(file_type(b"\x00\x00WEBP\0\0WEBP"), Value::Null);
+        assert_eq!(analyze(None, b"\x1f\x8b")["file_type"]["mime"], "application/gzip");
+    }
+
+    #[test]
+    fn analyze_empty() {
+        let analysis = analyze(None, b"");
+        assert_eq!(analysis["entropy"], 0.0);
+        assert_eq!(analysis["printable_ratio"], 0.0);
+        assert_eq!(analysis["utf8_valid"], true);
+        assert_eq!(analysis["windows"], json!([]));
+    }
+
+    #[test]
+    fn analyze_invalid_utf8() {
+        assert_eq!(valid_utf8_len(b"ab\xffcd\xe2\x82"), 4);
+        assert_eq!(valid_utf8_len(&["€".as_bytes(), b"\xe2\x28"].concat()), 4);
+        assert!(Analyze::new(Some(0)).execute(b"data").is_err());
+    }
+}
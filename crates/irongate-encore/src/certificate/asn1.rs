@@ -0,0 +1,259 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Write;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "registry")]
+use serde_json::{json, Value};
+
+use super::der::{self, Class, Tlv};
+use super::oid;
+use crate::encoding::pem;
+use crate::Operation;
+use crate::OperationError;
+#[cfg(feature = "registry")]
+use crate::{Category, OperationInfo};
+
+/// Render BER/DER values as an indented tree, one value per line. PEM input is decoded first.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Asn1Decode {}
+
+impl Operation for Asn1Decode {
+    fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
+        let mut output = vec![];
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        let mut decoded = vec![];
+        let input = if pem::is_pem(input) {
+            pem::decode_block(input, None, &mut decoded)?;
+            &decoded
+        } else {
+            input
+        };
+        let mut tree = String::new();
+        render(&mut tree, input, 0)?;
+        out.extend_from_slice(tree.as_bytes());
+        Ok(())
+    }
+}
+
+impl Asn1Decode {
+    pub const fn new() -> Self {
+        Asn1Decode {}
+    }
+}
+
+fn render(out: &mut String, mut input: &[u8], depth: usize) -> Result<(), OperationError> {
+    if input.is_empty() {
+        return Err(der::error("unexpected end"));
+    }
+    if depth > der::MAX_DEPTH {
+        return Err(der::error("nesting too deep"));
+    }
+    while !input.is_empty() {
+        let (tlv, rest) = der::read(input)?;
+        render_value(out, &tlv, depth)?;
+        input = rest;
+    }
+    Ok(())
+}
+
+fn render_value(out: &mut String, tlv: &Tlv, depth: usize) -> Result<(), OperationError> {
+    for _ in 0..depth {
+        out.push_str("  ");
+    }
+    let _ = match tlv.class {
+        Class::Universal => match type_name(tlv.tag) {
+            Some(name) => write!(out, "{name}"),
+            None => write!(out, "[UNIVERSAL {}]", tlv.tag),
+        },
+        Class::Application => write!(out, "[APPLICATION {}]", tlv.tag),
+        Class::ContextSpecific => write!(out, "[{}]", tlv.tag),
+        Class::Private => write!(out, "[PRIVATE {}]", tlv.tag),
+    };
+
+    if tlv.constructed {
+        out.push('\n');
+        return if tlv.content.is_empty() { Ok(()) } else { render(out, tlv.content, depth + 1) };
+    }
+
+    let content = tlv.content;
+    if tlv.class != Class::Universal {
+        if !content.is_empty()
+            && content.iter().all(|byte| byte.is_ascii_graphic() || *byte == b' ')
+        {
+            // Most implicitly tagged primitives in certificates are strings
+            let _ = write!(out, " '{}'", String::from_utf8_lossy(content));
+        } else {
+            push_hex(out, content);
+        }
+        out.push('\n');
+        return Ok(());
+    }
+
+    let mut encapsulated = content;
+    match tlv.tag {
+        der::BOOLEAN => {
+            out.push_str(if content.iter().any(|byte| *byte != 0) { " TRUE" } else { " FALSE" })
+        }
+        der::INTEGER | der::ENUMERATED => match der::integer(content) {
+            Some(value) => {
+                let _ = write!(out, " {value}");
+            }
+            None => push_hex(out, content),
+        },
+        der::BIT_STRING => {
+            let (unused, bits) =
+                content.split_first().ok_or_else(|| der::error("empty bit string"))?;
+            let _ = write!(out, " ({unused} unused bits)");
+            push_hex(out, bits);
+            encapsulated = if *unused == 0 { bits } else { &[] };
+        }
+        der::OBJECT_IDENTIFIER => {
+            let dotted = der::oid(content)?;
+            let _ = match oid::name(&dotted) {
+                Some(name) => write!(out, " {dotted} ({name})"),
+                None => write!(out, " {dotted}"),
+            };
+        }
+        der::NULL => {}
+        der::UTC_TIME | der::GENERALIZED_TIME => {
+            let _ = match der::time(tlv) {
+                Some(time) => write!(out, " {time}"),
+                None => write!(out, " {}", String::from_utf8_lossy(content)),
+            };
+        }
+        _ => match der::string(tlv) {
+            Some(text) => {
+                let _ = write!(out, " '{text}'");
+            }
+            None => push_hex(out, content),
+        },
+    }
+    out.push('\n');
+
+    // Keys and extension values are DER wrapped in bit and octet strings
+    if matches!(tlv.tag, der::BIT_STRING | der::OCTET_STRING)
+        && matches!(encapsulated.first(), Some(0x30 | 0x31))
+    {
+        let mut nested = String::new();
+        if render(&mut nested, encapsulated, depth + 1).is_ok() {
+            out.push_str(&nested);
+        }
+    }
+    Ok(())
+}
+
+fn type_name(tag: u32) -> Option<&'static str> {
+    Some(match tag {
+        0 => "END OF CONTENTS",
+        der::BOOLEAN => "BOOLEAN",
+        der::INTEGER => "INTEGER",
+        der::BIT_STRING => "BIT STRING",
+        der::OCTET_STRING => "OCTET STRING",
+        der::NULL => "NULL",
+        der::OBJECT_IDENTIFIER => "OBJECT IDENTIFIER",
+        der::ENUMERATED => "ENUMERATED",
+        der::UTF8_STRING => "UTF8String",
+        der::SEQUENCE => "SEQUENCE",
+        der::SET => "SET",
+        der::NUMERIC_STRING => "NumericString",
+        der::PRINTABLE_STRING => "PrintableString",
+        der::T61_STRING => "T61String",
+        der::IA5_STRING => "IA5String",
+        der::UTC_TIME => "UTCTime",
+        der::GENERALIZED_TIME => "GeneralizedTime",
+        der::VISIBLE_STRING => "VisibleString",
+        der::UNIVERSAL_STRING => "UniversalString",
+        der::BMP_STRING => "BMPString",
+        _ => return None,
+    })
+}
+
+fn push_hex(out: &mut String, bytes: &[u8]) {
+    if bytes.is_empty() {
+        return;
+    }
+    out.push(' ');
+    for byte in bytes {
+        let _ = write!(out, "{byte:02x}");
+    }
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for Asn1Decode {
+    const ID: &'static str = "asn1_decode";
+    const NAME: &'static str = "ASN.1 Decode";
+    const CATEGORY: Category = Category::Certificate;
+    const DESCRIPTION: &'static str =
+        "Render a BER/DER (or PEM) structure as a tree with object identifiers resolved";
+
+    fn options_schema() -> Value {
+        json!({ "type": "object", "properties": {} })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(input: &[u8]) -> String {
+        String::from_utf8(Asn1Decode::new().execute(input).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn asn1_decode_algorithm_identifier() {
+        let input = [
+            0x30, 0x0d, 0x06, 0x09, 0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0b, 0x05,
+            0x00,
+        ];
+        let expected = "SEQUENCE\n  OBJECT IDENTIFIER 1.2.840.113549.1.1.11 (sha256WithRSAEncryption)\n  NULL\n";
+        assert_eq!(decode(&input), expected);
+    }
+
+    #[test]
+    fn asn1_decode_values() {
+        let input = [
+            0x31, 0x80, 0x01, 0x01, 0xff, 0x02, 0x02, 0xff, 0x7f, 0x0c, 0x03, 0xe2, 0x82, 0xac,
+            0x17, 0x0d, b'2', b'4', b'0', b'1', b'0', b'1', b'0', b'0', b'0', b'0', b'0', b'0',
+            b'Z', 0x82, 0x02, b'i', b'g', 0x81, 0x01, 0x00, 0x00, 0x00,
+        ];
+        let expected = "SET\n  BOOLEAN TRUE\n  INTEGER -129\n  UTF8String '€'\n  \
+            UTCTime 2024-01-01T00:00:00Z\n  [2] 'ig'\n  [1] 00\n";
+        assert_eq!(decode(&input), expected);
+    }
+
+    #[test]
+    fn asn1_decode_encapsulated() {
+        let input = [0x04, 0x05, 0x30, 0x03, 0x02, 0x01, 0x07, 0x03, 0x03, 0x00, 0x30, 0x00];
+        let expected = "OCTET STRING 3003020107\n  SEQUENCE\n    INTEGER 7\nBIT STRING (0 unused bits) 3000\n  SEQUENCE\n";
+        assert_eq!(decode(&input), expected);
+        // Not a nested structure after all
+        assert_eq!(decode(&[0x04, 0x02, 0x30, 0x05]), "OCTET STRING 3005\n");
+    }
+
+    #[test]
+    fn asn1_decode_pem() {
+        let input = "-----BEGIN PARAMETERS-----\nBggqhkjOPQMBBw==\n-----END PARAMETERS-----\n";
+        assert_eq!(
+            decode(input.as_bytes()),
+            "OBJECT IDENTIFIER 1.2.840.10045.3.1.7 (prime256v1)\n"
+        );
+    }
+
+    #[test]
+    fn asn1_decode_errors() {
+        let decoder = Asn1Decode::new();
+        assert!(decoder.execute(b"").is_err());
+        assert!(decoder.execute(&[0x30, 0x03, 0x02, 0x01]).is_err());
+        assert!(decoder.execute(&[0x06, 0x00]).is_err());
+        let nested: Vec<u8> =
+            (0..100).rev().flat_map(|depth| [0x30, 2 * depth as u8]).collect::<Vec<_>>();
+        assert!(decoder.execute(&nested).is_err());
+    }
+}
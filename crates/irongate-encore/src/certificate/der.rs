@@ -0,0 +1,336 @@
+//! Minimal reader for BER and DER encoded ASN.1 values.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt::Write;
+
+use crate::OperationError;
+
+/// Deepest nesting accepted, guards the recursive readers against crafted input
+pub(crate) const MAX_DEPTH: usize = 64;
+
+pub(crate) const BOOLEAN: u32 = 1;
+pub(crate) const INTEGER: u32 = 2;
+pub(crate) const BIT_STRING: u32 = 3;
+pub(crate) const OCTET_STRING: u32 = 4;
+pub(crate) const NULL: u32 = 5;
+pub(crate) const OBJECT_IDENTIFIER: u32 = 6;
+pub(crate) const ENUMERATED: u32 = 10;
+pub(crate) const UTF8_STRING: u32 = 12;
+pub(crate) const SEQUENCE: u32 = 16;
+pub(crate) const SET: u32 = 17;
+pub(crate) const NUMERIC_STRING: u32 = 18;
+pub(crate) const PRINTABLE_STRING: u32 = 19;
+pub(crate) const T61_STRING: u32 = 20;
+pub(crate) const IA5_STRING: u32 = 22;
+pub(crate) const UTC_TIME: u32 = 23;
+pub(crate) const GENERALIZED_TIME: u32 = 24;
+pub(crate) const VISIBLE_STRING: u32 = 26;
+pub(crate) const UNIVERSAL_STRING: u32 = 28;
+pub(crate) const BMP_STRING: u32 = 30;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Class {
+    Universal,
+    Application,
+    ContextSpecific,
+    Private,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Tlv<'a> {
+    pub(crate) class: Class,
+    pub(crate) constructed: bool,
+    pub(crate) tag: u32,
+    /// Content octets, without the end-of-contents marker of indefinite lengths
+    pub(crate) content: &'a [u8],
+}
+
+pub(crate) fn error(message: &str) -> OperationError {
+    OperationError::DecodeError { message: format!("Invalid ASN.1: {message}"), offset: None }
+}
+
+/// Read the value at the start of `input` and return it with the bytes following it
+pub(crate) fn read(input: &[u8]) -> Result<(Tlv<'_>, &[u8]), OperationError> {
+    read_nested(input, 0)
+}
+
+fn read_nested(input: &[u8], depth: usize) -> Result<(Tlv<'_>, &[u8]), OperationError> {
+    if depth > MAX_DEPTH {
+        return Err(error("nesting too deep"));
+    }
+    let (&identifier, mut rest) = input.split_first().ok_or_else(|| error("unexpected end"))?;
+    let class = match identifier >> 6 {
+        0 => Class::Universal,
+        1 => Class::Application,
+        2 => Class::ContextSpecific,
+        _ => Class::Private,
+    };
+    let constructed = identifier & 0x20 != 0;
+    let mut tag = u32::from(identifier & 0x1f);
+    if tag == 0x1f {
+        tag = 0;
+        loop {
+            let (&byte, next) = rest.split_first().ok_or_else(|| error("unexpected end"))?;
+            rest = next;
+            if tag > u32::MAX >> 7 {
+                return Err(error("tag number too large"));
+            }
+            tag = tag << 7 | u32::from(byte & 0x7f);
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+    }
+
+    let (&length, mut rest) = rest.split_first().ok_or_else(|| error("unexpected end"))?;
+    if length == 0x80 {
+        if !constructed {
+            return Err(error("indefinite length on a primitive value"));
+        }
+        let mut remaining = rest;
+        while !remaining.starts_with(&[0, 0]) {
+            remaining = read_nested(remaining, depth + 1)?.1;
+        }
+        let content = &rest[..rest.len() - remaining.len()];
+        return Ok((Tlv { class, constructed, tag, content }, &remaining[2..]));
+    }
+    let length = if length & 0x80 == 0 {
+        usize::from(length)
+    } else {
+        let count = usize::from(length & 0x7f);
+        if count > core::mem::size_of::<usize>() {
+            return Err(error("length too large"));
+        }
+        let bytes = rest.get(..count).ok_or_else(|| error("unexpected end"))?;
+        rest = &rest[count..];
+        bytes.iter().fold(0, |length, byte| length << 8 | usize::from(*byte))
+    };
+    if length > rest.len() {
+        return Err(error("value exceeds input"));
+    }
+    Ok((Tlv { class, constructed, tag, content: &rest[..length] }, &rest[length..]))
+}
+
+impl<'a> Tlv<'a> {
+    pub(crate) fn is(&self, tag: u32) -> bool {
+        self.class == Class::Universal && self.tag == tag
+    }
+
+    pub(crate) fn is_context(&self, tag: u32) -> bool {
+        self.class == Class::ContextSpecific && self.tag == tag
+    }
+
+    pub(crate) fn children(&self) -> Children<'a> {
+        Children { rest: if self.constructed { self.content } else { &[] } }
+    }
+}
+
+pub(crate) struct Children<'a> {
+    rest: &'a [u8],
+}
+
+impl<'a> Iterator for Children<'a> {
+    type Item = Result<Tlv<'a>, OperationError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        match read(self.rest) {
+            Ok((tlv, rest)) => {
+                self.rest = rest;
+                Some(Ok(tlv))
+            }
+            Err(err) => {
+                self.rest = &[];
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+/// Dotted representation of an object identifier
+pub(crate) fn oid(content: &[u8]) -> Result<String, OperationError> {
+    let mut arcs = content.split_inclusive(|byte| byte & 0x80 == 0);
+    let mut dotted = String::new();
+    let mut first = true;
+    for arc in arcs.by_ref() {
+        if arc.len() > 9 || arc.last().is_some_and(|byte| byte & 0x80 != 0) {
+            return Err(error("malformed object identifier"));
+        }
+        let value = arc.iter().fold(0u64, |value, byte| value << 7 | u64::from(byte & 0x7f));
+        if first {
+            let (root, value) = match value {
+                0..=39 => (0, value),
+                40..=79 => (1, value - 40),
+                _ => (2, value - 80),
+            };
+            let _ = write!(dotted, "{root}.{value}");
+            first = false;
+        } else {
+            let _ = write!(dotted, ".{value}");
+        }
+    }
+    if first {
+        return Err(error("empty object identifier"));
+    }
+    Ok(dotted)
+}
+
+/// Text of the string types, `None` for any other value
+pub(crate) fn string(tlv: &Tlv) -> Option<String> {
+    if tlv.class != Class::Universal || tlv.constructed {
+        return None;
+    }
+    match tlv.tag {
+        UTF8_STRING | NUMERIC_STRING | PRINTABLE_STRING | IA5_STRING | VISIBLE_STRING => {
+            Some(String::from_utf8_lossy(tlv.content).to_string())
+        }
+        // Teletex is mostly used for Latin-1 text in practice
+        T61_STRING => Some(tlv.content.iter().map(|byte| char::from(*byte)).collect()),
+        BMP_STRING => Some(
+            char::decode_utf16(
+                tlv.content.chunks_exact(2).map(|pair| u16::from_be_bytes([pair[0], pair[1]])),
+            )
+            .map(|ch| ch.unwrap_or(char::REPLACEMENT_CHARACTER))
+            .collect(),
+        ),
+        UNIVERSAL_STRING => Some(
+            tlv.content
+                .chunks_exact(4)
+                .map(|quad| u32::from_be_bytes([quad[0], quad[1], quad[2], quad[3]]))
+                .map(|code| char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER))
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+/// ISO 8601 form of a UTCTime or GeneralizedTime
+pub(crate) fn time(tlv: &Tlv) -> Option<String> {
+    let text = core::str::from_utf8(tlv.content).ok().filter(|text| text.is_ascii())?;
+    let (year, rest) = if tlv.is(UTC_TIME) {
+        // RFC 5280 maps two digit years to 1950-2049
+        let year: u32 = text.get(..2)?.parse().ok()?;
+        (if year >= 50 { 1900 + year } else { 2000 + year }, &text[2..])
+    } else if tlv.is(GENERALIZED_TIME) {
+        (text.get(..4)?.parse().ok()?, &text[4..])
+    } else {
+        return None;
+    };
+
+    let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+    if digits != 8 && digits != 10 {
+        return None;
+    }
+    let seconds = rest.get(8..digits).filter(|seconds| !seconds.is_empty()).unwrap_or("00");
+    let mut zone = &rest[digits..];
+    let mut fraction = "";
+    if zone.starts_with('.') {
+        let len = 1 + zone[1..].bytes().take_while(u8::is_ascii_digit).count();
+        (fraction, zone) = zone.split_at(len);
+    }
+    let zone = match zone {
+        "" | "Z" => zone.to_string(),
+        _ if zone.len() == 5 && (zone.starts_with('+') || zone.starts_with('-')) => {
+            format!("{}:{}", &zone[..3], &zone[3..])
+        }
+        _ => return None,
+    };
+    Some(format!(
+        "{year:04}-{}-{}T{}:{}:{seconds}{fraction}{zone}",
+        &rest[..2],
+        &rest[2..4],
+        &rest[4..6],
+        &rest[6..8]
+    ))
+}
+
+/// Big-endian two's complement integer, `None` when it does not fit an `i64`
+pub(crate) fn integer(content: &[u8]) -> Option<i64> {
+    if content.is_empty() || content.len() > 8 {
+        return None;
+    }
+    let sign = if content[0] & 0x80 != 0 { -1 } else { 0 };
+    Some(content.iter().fold(sign, |value, byte| value << 8 | i64::from(*byte)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn der_read_long_form() {
+        let mut input = vec![0x04, 0x82, 0x01, 0x00];
+        input.extend_from_slice(&[0xaa; 256]);
+        input.push(0x05);
+        let (tlv, rest) = read(&input).unwrap();
+        assert!(tlv.is(OCTET_STRING));
+        assert_eq!(tlv.content.len(), 256);
+        assert_eq!(rest, [0x05]);
+    }
+
+    #[test]
+    fn der_read_high_tag_number() {
+        let (tlv, _) = read(&[0xbf, 0x81, 0x00, 0x01, 0xff]).unwrap();
+        assert_eq!(tlv.class, Class::ContextSpecific);
+        assert!(tlv.constructed);
+        assert_eq!(tlv.tag, 128);
+    }
+
+    #[test]
+    fn ber_read_indefinite_length() {
+        let input = [0x30, 0x80, 0x02, 0x01, 0x05, 0x30, 0x80, 0x00, 0x00, 0x00, 0x00, 0x01];
+        let (tlv, rest) = read(&input).unwrap();
+        assert_eq!(tlv.content, &input[2..9]);
+        assert_eq!(tlv.children().count(), 2);
+        assert_eq!(rest, [0x01]);
+    }
+
+    #[test]
+    fn der_read_errors() {
+        assert!(read(&[0x30, 0x05, 0x02, 0x01]).is_err());
+        assert!(read(&[0x04, 0x80, 0x00, 0x00]).is_err());
+        assert!(read(&[0x04, 0x89, 0x01, 0, 0, 0, 0, 0, 0, 0, 0]).is_err());
+        assert!(read(&[0x30, 0x80, 0x02, 0x01, 0x05]).is_err());
+        let nested: Vec<u8> = [[0x30, 0x80]; 100].concat();
+        assert!(read(&nested).is_err());
+    }
+
+    #[test]
+    fn der_oid() {
+        assert_eq!(
+            oid(&[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0b]).unwrap(),
+            "1.2.840.113549.1.1.11"
+        );
+        assert_eq!(oid(&[0x55, 0x04, 0x03]).unwrap(), "2.5.4.3");
+        assert_eq!(oid(&[0x88, 0x37, 0x01]).unwrap(), "2.999.1");
+        assert!(oid(&[]).is_err());
+        assert!(oid(&[0x2a, 0x86]).is_err());
+    }
+
+    #[test]
+    fn der_time() {
+        let utc = Tlv {
+            class: Class::Universal,
+            constructed: false,
+            tag: UTC_TIME,
+            content: b"491231235959Z",
+        };
+        assert_eq!(time(&utc).unwrap(), "2049-12-31T23:59:59Z");
+        let utc = Tlv { content: b"5001010000+0130", ..utc };
+        assert_eq!(time(&utc).unwrap(), "1950-01-01T00:00:00+01:30");
+        let generalized = Tlv { tag: GENERALIZED_TIME, content: b"20240229120000.25Z", ..utc };
+        assert_eq!(time(&generalized).unwrap(), "2024-02-29T12:00:00.25Z");
+        assert!(time(&Tlv { content: b"2024", ..generalized }).is_none());
+    }
+
+    #[test]
+    fn der_integer() {
+        assert_eq!(integer(&[0x01, 0x00]), Some(256));
+        assert_eq!(integer(&[0xff]), Some(-1));
+        assert_eq!(integer(&[0x00, 0xff]), Some(255));
+        assert_eq!(integer(&[0x01; 9]), None);
+    }
+}
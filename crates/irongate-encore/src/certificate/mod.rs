@@ -0,0 +1,4 @@
+pub mod asn1;
+mod der;
+mod oid;
+pub mod x509;
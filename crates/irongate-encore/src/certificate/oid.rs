@@ -0,0 +1,107 @@
+//! Names of the object identifiers commonly found in keys and certificates.
+
+static NAMES: &[(&str, &str)] = &[
+    // Signature and key algorithms
+    ("1.2.840.113549.1.1.1", "rsaEncryption"),
+    ("1.2.840.113549.1.1.4", "md5WithRSAEncryption"),
+    ("1.2.840.113549.1.1.5", "sha1WithRSAEncryption"),
+    ("1.2.840.113549.1.1.10", "rsassaPss"),
+    ("1.2.840.113549.1.1.11", "sha256WithRSAEncryption"),
+    ("1.2.840.113549.1.1.12", "sha384WithRSAEncryption"),
+    ("1.2.840.113549.1.1.13", "sha512WithRSAEncryption"),
+    ("1.2.840.113549.1.1.14", "sha224WithRSAEncryption"),
+    ("1.2.840.10040.4.1", "dsa"),
+    ("1.2.840.10040.4.3", "dsa-with-sha1"),
+    ("1.2.840.10045.2.1", "id-ecPublicKey"),
+    ("1.2.840.10045.4.1", "ecdsa-with-SHA1"),
+    ("1.2.840.10045.4.3.2", "ecdsa-with-SHA256"),
+    ("1.2.840.10045.4.3.3", "ecdsa-with-SHA384"),
+    ("1.2.840.10045.4.3.4", "ecdsa-with-SHA512"),
+    ("1.2.840.10045.3.1.7", "prime256v1"),
+    ("1.3.132.0.10", "secp256k1"),
+    ("1.3.132.0.34", "secp384r1"),
+    ("1.3.132.0.35", "secp521r1"),
+    ("1.3.101.110", "X25519"),
+    ("1.3.101.111", "X448"),
+    ("1.3.101.112", "Ed25519"),
+    ("1.3.101.113", "Ed448"),
+    // Digests
+    ("1.2.840.113549.2.5", "md5"),
+    ("1.3.14.3.2.26", "sha1"),
+    ("2.16.840.1.101.3.4.2.1", "sha256"),
+    ("2.16.840.1.101.3.4.2.2", "sha384"),
+    ("2.16.840.1.101.3.4.2.3", "sha512"),
+    ("2.16.840.1.101.3.4.2.4", "sha224"),
+    // PKCS #7, #9 and #12
+    ("1.2.840.113549.1.7.1", "data"),
+    ("1.2.840.113549.1.7.2", "signedData"),
+    ("1.2.840.113549.1.7.3", "envelopedData"),
+    ("1.2.840.113549.1.7.6", "encryptedData"),
+    ("1.2.840.113549.1.9.1", "emailAddress"),
+    ("1.2.840.113549.1.9.3", "contentType"),
+    ("1.2.840.113549.1.9.4", "messageDigest"),
+    ("1.2.840.113549.1.9.5", "signingTime"),
+    ("1.2.840.113549.1.9.14", "extensionRequest"),
+    ("1.2.840.113549.1.5.13", "pbes2"),
+    ("1.2.840.113549.1.5.12", "pbkdf2"),
+    ("2.16.840.1.101.3.4.1.2", "aes128-CBC"),
+    ("2.16.840.1.101.3.4.1.42", "aes256-CBC"),
+    // Distinguished name attributes
+    ("2.5.4.3", "commonName"),
+    ("2.5.4.4", "surname"),
+    ("2.5.4.5", "serialNumber"),
+    ("2.5.4.6", "countryName"),
+    ("2.5.4.7", "localityName"),
+    ("2.5.4.8", "stateOrProvinceName"),
+    ("2.5.4.9", "streetAddress"),
+    ("2.5.4.10", "organizationName"),
+    ("2.5.4.11", "organizationalUnitName"),
+    ("2.5.4.12", "title"),
+    ("2.5.4.42", "givenName"),
+    ("0.9.2342.19200300.100.1.1", "userId"),
+    ("0.9.2342.19200300.100.1.25", "domainComponent"),
+    // Certificate extensions
+    ("2.5.29.14", "subjectKeyIdentifier"),
+    ("2.5.29.15", "keyUsage"),
+    ("2.5.29.17", "subjectAltName"),
+    ("2.5.29.18", "issuerAltName"),
+    ("2.5.29.19", "basicConstraints"),
+    ("2.5.29.30", "nameConstraints"),
+    ("2.5.29.31", "cRLDistributionPoints"),
+    ("2.5.29.32", "certificatePolicies"),
+    ("2.5.29.35", "authorityKeyIdentifier"),
+    ("2.5.29.37", "extKeyUsage"),
+    ("1.3.6.1.5.5.7.1.1", "authorityInfoAccess"),
+    ("1.3.6.1.4.1.11129.2.4.2", "ctPrecertificateSCTs"),
+    ("1.3.6.1.5.5.7.3.1", "serverAuth"),
+    ("1.3.6.1.5.5.7.3.2", "clientAuth"),
+    ("1.3.6.1.5.5.7.3.3", "codeSigning"),
+    ("1.3.6.1.5.5.7.3.4", "emailProtection"),
+    ("1.3.6.1.5.5.7.3.8", "timeStamping"),
+    ("1.3.6.1.5.5.7.3.9", "OCSPSigning"),
+    ("1.3.6.1.5.5.7.48.1", "ocsp"),
+    ("1.3.6.1.5.5.7.48.2", "caIssuers"),
+    ("2.23.140.1.2.1", "domain-validated"),
+    ("2.23.140.1.2.2", "organization-validated"),
+];
+
+/// Short forms used when printing distinguished names, as done by OpenSSL
+static ATTRIBUTES: &[(&str, &str)] = &[
+    ("2.5.4.3", "CN"),
+    ("2.5.4.6", "C"),
+    ("2.5.4.7", "L"),
+    ("2.5.4.8", "ST"),
+    ("2.5.4.9", "street"),
+    ("2.5.4.10", "O"),
+    ("2.5.4.11", "OU"),
+    ("0.9.2342.19200300.100.1.1", "UID"),
+    ("0.9.2342.19200300.100.1.25", "DC"),
+];
+
+pub(crate) fn name(oid: &str) -> Option<&'static str> {
+    NAMES.iter().find(|(known, _)| *known == oid).map(|(_, name)| *name)
+}
+
+pub(crate) fn attribute(oid: &str) -> Option<&'static str> {
+    ATTRIBUTES.iter().find(|(known, _)| *known == oid).map(|(_, name)| *name).or_else(|| name(oid))
+}
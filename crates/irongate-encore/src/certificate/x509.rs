@@ -0,0 +1,366 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Write;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::der::{self, Tlv};
+use super::oid;
+use crate::encoding::pem;
+use crate::Operation;
+use crate::OperationError;
+#[cfg(feature = "registry")]
+use crate::{Category, OperationInfo};
+use crate::{HexEncode, HexFormat, Sha1Hash, Sha2Hash, Sha2Version};
+
+/// Summarise a DER or PEM certificate as JSON
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct X509Decode {}
+
+impl Operation for X509Decode {
+    fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
+        let mut output = vec![];
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        let mut decoded = vec![];
+        let input = if pem::is_pem(input) {
+            pem::decode_block(input, Some("CERTIFICATE"), &mut decoded)?;
+            &decoded
+        } else {
+            input
+        };
+        let certificate = decode(input)?;
+        out.extend_from_slice(certificate.to_string().as_bytes());
+        Ok(())
+    }
+}
+
+impl X509Decode {
+    pub const fn new() -> Self {
+        X509Decode {}
+    }
+}
+
+fn invalid(what: &str) -> OperationError {
+    OperationError::DecodeError {
+        message: format!("Invalid X.509 certificate: {what}"),
+        offset: None,
+    }
+}
+
+fn expect<'a>(tlv: Option<Tlv<'a>>, tag: u32, what: &str) -> Result<Tlv<'a>, OperationError> {
+    tlv.filter(|tlv| tlv.is(tag)).ok_or_else(|| invalid(what))
+}
+
+fn children<'a>(tlv: &Tlv<'a>) -> Result<vec::IntoIter<Tlv<'a>>, OperationError> {
+    Ok(tlv.children().collect::<Result<Vec<_>, _>>()?.into_iter())
+}
+
+fn decode(input: &[u8]) -> Result<Value, OperationError> {
+    let (certificate, rest) = der::read(input)?;
+    let der = &input[..input.len() - rest.len()];
+    let mut parts = children(&expect(Some(certificate), der::SEQUENCE, "certificate")?)?;
+    let tbs = expect(parts.next(), der::SEQUENCE, "tbsCertificate")?;
+    let signature_algorithm =
+        algorithm(&expect(parts.next(), der::SEQUENCE, "signatureAlgorithm")?)?;
+
+    let mut fields = children(&tbs)?.peekable();
+    let version = match fields.next_if(|field| field.is_context(0)) {
+        Some(version) => {
+            let version = expect(version.children().next().transpose()?, der::INTEGER, "version")?;
+            der::integer(version.content).ok_or_else(|| invalid("version"))? + 1
+        }
+        None => 1,
+    };
+    let serial = expect(fields.next(), der::INTEGER, "serialNumber")?;
+    expect(fields.next(), der::SEQUENCE, "signature")?;
+    let issuer = name(&expect(fields.next(), der::SEQUENCE, "issuer")?)?;
+    let mut validity = children(&expect(fields.next(), der::SEQUENCE, "validity")?)?;
+    let not_before = validity.next().and_then(|time| der::time(&time));
+    let not_after = validity.next().and_then(|time| der::time(&time));
+    let subject = name(&expect(fields.next(), der::SEQUENCE, "subject")?)?;
+    let public_key = public_key(&expect(fields.next(), der::SEQUENCE, "subjectPublicKeyInfo")?)?;
+
+    let mut subject_alt_names = vec![];
+    for field in fields.filter(|field| field.is_context(3)) {
+        let extensions = expect(field.children().next().transpose()?, der::SEQUENCE, "extensions")?;
+        for extension in extensions.children() {
+            let mut extension = children(&extension?)?;
+            let id = expect(extension.next(), der::OBJECT_IDENTIFIER, "extension")?;
+            // The criticality flag is optional, the value is always the last field
+            let value = expect(extension.last(), der::OCTET_STRING, "extension value")?;
+            if der::oid(id.content)? == "2.5.29.17" {
+                subject_alt_names = alt_names(value.content)?;
+            }
+        }
+    }
+
+    // Leading zeros only carry the sign
+    let serial = match serial.content {
+        [0, rest @ ..] if !rest.is_empty() => rest,
+        serial => serial,
+    };
+    Ok(json!({
+        "version": version,
+        "serial": hex(HexFormat::Lower, serial)?,
+        "signature_algorithm": signature_algorithm,
+        "issuer": issuer,
+        "subject": subject,
+        "validity": { "not_before": not_before, "not_after": not_after },
+        "subject_alt_names": subject_alt_names,
+        "public_key": public_key,
+        "fingerprints": {
+            "sha1": hex(HexFormat::Upper, &Sha1Hash::new().execute(der)?)?,
+            "sha256": hex(HexFormat::Upper, &Sha2Hash::new(Sha2Version::Sha256).execute(der)?)?,
+        },
+    }))
+}
+
+/// Colon separated hex, as printed by OpenSSL
+fn hex(format: HexFormat, bytes: &[u8]) -> Result<String, OperationError> {
+    let encoded = HexEncode::new(format, None, Some(":".to_string())).execute(bytes)?;
+    Ok(String::from_utf8(encoded)?)
+}
+
+fn oid_name(tlv: &Tlv) -> Result<String, OperationError> {
+    let dotted = der::oid(tlv.content)?;
+    Ok(oid::name(&dotted).map(str::to_string).unwrap_or(dotted))
+}
+
+fn algorithm(identifier: &Tlv) -> Result<String, OperationError> {
+    oid_name(&expect(
+        identifier.children().next().transpose()?,
+        der::OBJECT_IDENTIFIER,
+        "algorithm",
+    )?)
+}
+
+/// One-line form of a distinguished name, e.g. `C=US, O=IronGate, CN=irongate.test`
+fn name(name: &Tlv) -> Result<String, OperationError> {
+    let mut text = String::new();
+    for set in name.children() {
+        for attribute in set?.children() {
+            let mut attribute = children(&attribute?)?;
+            let id = der::oid(expect(attribute.next(), der::OBJECT_IDENTIFIER, "name")?.content)?;
+            let value = attribute.next().ok_or_else(|| invalid("name"))?;
+            if !text.is_empty() {
+                text.push_str(", ");
+            }
+            let _ = match oid::attribute(&id) {
+                Some(short) => write!(text, "{short}="),
+                None => write!(text, "{id}="),
+            };
+            match der::string(&value) {
+                Some(value) => text.push_str(&value),
+                None => {
+                    text.push('#');
+                    text.push_str(&hex(HexFormat::Lower, value.content)?.replace(':', ""));
+                }
+            }
+        }
+    }
+    Ok(text)
+}
+
+fn public_key(info: &Tlv) -> Result<Value, OperationError> {
+    let mut info = children(info)?;
+    let identifier = expect(info.next(), der::SEQUENCE, "public key algorithm")?;
+    let key = expect(info.next(), der::BIT_STRING, "public key")?;
+    let key = key.content.get(1..).unwrap_or_default();
+    let mut identifier = children(&identifier)?;
+    let algorithm =
+        der::oid(expect(identifier.next(), der::OBJECT_IDENTIFIER, "algorithm")?.content)?;
+    let parameters = identifier.next();
+
+    let (key_type, bits, curve) = match algorithm.as_str() {
+        "1.2.840.113549.1.1.1" | "1.2.840.113549.1.1.10" => {
+            let modulus = der::read(key).ok().and_then(|(key, _)| key.children().next()?.ok());
+            ("RSA", modulus.map(|modulus| bit_length(modulus.content)), None)
+        }
+        "1.2.840.10040.4.1" => {
+            let prime = parameters.and_then(|parameters| parameters.children().next()?.ok());
+            ("DSA", prime.map(|prime| bit_length(prime.content)), None)
+        }
+        "1.2.840.10045.2.1" => {
+            let curve = match parameters.filter(|parameters| parameters.is(der::OBJECT_IDENTIFIER))
+            {
+                Some(curve) => Some(oid_name(&curve)?),
+                None => None,
+            };
+            let bits = match curve.as_deref() {
+                Some("prime256v1" | "secp256k1") => Some(256),
+                Some("secp384r1") => Some(384),
+                Some("secp521r1") => Some(521),
+                _ => None,
+            };
+            ("EC", bits, curve)
+        }
+        "1.3.101.110" => ("X25519", Some(253), None),
+        "1.3.101.111" => ("X448", Some(448), None),
+        "1.3.101.112" => ("Ed25519", Some(256), None),
+        "1.3.101.113" => ("Ed448", Some(456), None),
+        _ => ("unknown", None, None),
+    };
+    Ok(json!({
+        "type": key_type,
+        "algorithm": oid::name(&algorithm).unwrap_or(algorithm.as_str()),
+        "bits": bits,
+        "curve": curve,
+    }))
+}
+
+fn bit_length(integer: &[u8]) -> usize {
+    let digits = match integer.iter().position(|byte| *byte != 0) {
+        Some(start) => &integer[start..],
+        None => return 0,
+    };
+    digits.len() * 8 - digits[0].leading_zeros() as usize
+}
+
+fn alt_names(value: &[u8]) -> Result<Vec<String>, OperationError> {
+    let (names, _) = der::read(value)?;
+    let mut alt_names = vec![];
+    for name in expect(Some(names), der::SEQUENCE, "subjectAltName")?.children() {
+        let name = name?;
+        let text = String::from_utf8_lossy(name.content);
+        alt_names.push(match name.tag {
+            1 => format!("email:{text}"),
+            2 => format!("DNS:{text}"),
+            6 => format!("URI:{text}"),
+            4 => format!(
+                "DirName:{}",
+                self::name(&expect(
+                    name.children().next().transpose()?,
+                    der::SEQUENCE,
+                    "directoryName"
+                )?)?
+            ),
+            7 => format!("IP:{}", ip_address(name.content)),
+            tag => format!("[{tag}]"),
+        });
+    }
+    Ok(alt_names)
+}
+
+fn ip_address(bytes: &[u8]) -> String {
+    let mut text = String::new();
+    match bytes.len() {
+        4 => {
+            let _ = write!(text, "{}.{}.{}.{}", bytes[0], bytes[1], bytes[2], bytes[3]);
+        }
+        16 => {
+            for (i, group) in bytes.chunks_exact(2).enumerate() {
+                let separator = if i > 0 { ":" } else { "" };
+                let _ = write!(text, "{separator}{:x}", u16::from_be_bytes([group[0], group[1]]));
+            }
+        }
+        _ => {
+            for byte in bytes {
+                let _ = write!(text, "{byte:02x}");
+            }
+        }
+    }
+    text
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for X509Decode {
+    const ID: &'static str = "x509_decode";
+    const NAME: &'static str = "X.509 Decode";
+    const CATEGORY: Category = Category::Certificate;
+    const DESCRIPTION: &'static str =
+        "Summarise a DER or PEM certificate: names, validity, public key and fingerprints";
+
+    fn options_schema() -> Value {
+        json!({ "type": "object", "properties": {} })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CERTIFICATE: &str = "-----BEGIN CERTIFICATE-----
+MIICDjCCAbOgAwIBAgIEGis8TTAKBggqhkjOPQQDAjA4MQswCQYDVQQGEwJVUzER
+MA8GA1UECgwISXJvbkdhdGUxFjAUBgNVBAMMDWlyb25nYXRlLnRlc3QwHhcNMjQw
+MTAxMDAwMDAwWhcNMzQwMTAxMDAwMDAwWjA4MQswCQYDVQQGEwJVUzERMA8GA1UE
+CgwISXJvbkdhdGUxFjAUBgNVBAMMDWlyb25nYXRlLnRlc3QwWTATBgcqhkjOPQIB
+BggqhkjOPQMBBwNCAAQVuvP/4BsQns5Dawf1hjCWTPUVG5tvYVygowfqnGkZVmP+
+kYt4lWmz+gVFnwnYR2jvhX6w1a+uu5BbnbIOPLTOo4GqMIGnMB0GA1UdDgQWBBRK
+yFzpo+95rJo0BsQ0ewH8kyFUhzAfBgNVHSMEGDAWgBRKyFzpo+95rJo0BsQ0ewH8
+kyFUhzAPBgNVHRMBAf8EBTADAQH/MFQGA1UdEQRNMEuCDWlyb25nYXRlLnRlc3SC
+DyouaXJvbmdhdGUudGVzdIcEfwAAAYcQAAAAAAAAAAAAAAAAAAAAAYERb3BzQGly
+b25nYXRlLnRlc3QwCgYIKoZIzj0EAwIDSQAwRgIhAPl9RAftD4usR1ElBbFkf8SJ
+/ARFX12PopsuG1QIXI+oAiEApY7eyxRbmSVxk4sB32GS48hG/SI8KWdRuhGsU3AX
+xzc=
+-----END CERTIFICATE-----
+";
+
+    #[test]
+    fn x509_decode_pem() {
+        let output = X509Decode::new().execute(CERTIFICATE.as_bytes()).unwrap();
+        let actual: Value = serde_json::from_slice(&output).unwrap();
+        let expected = json!({
+            "version": 3,
+            "serial": "1a:2b:3c:4d",
+            "signature_algorithm": "ecdsa-with-SHA256",
+            "issuer": "C=US, O=IronGate, CN=irongate.test",
+            "subject": "C=US, O=IronGate, CN=irongate.test",
+            "validity": {
+                "not_before": "2024-01-01T00:00:00Z",
+                "not_after": "2034-01-01T00:00:00Z"
+            },
+            "subject_alt_names": [
+                "DNS:irongate.test",
+                "DNS:*.irongate.test",
+                "IP:127.0.0.1",
+                "IP:0:0:0:0:0:0:0:1",
+                "email:ops@irongate.test"
+            ],
+            "public_key": {
+                "type": "EC",
+                "algorithm": "id-ecPublicKey",
+                "bits": 256,
+                "curve": "prime256v1"
+            },
+            "fingerprints": {
+                "sha1": "41:B6:25:E9:C4:C6:50:0A:63:3D:69:31:3E:64:3A:34:76:89:0F:A3",
+                "sha256": "C3:74:A5:B7:8B:52:8C:FD:41:77:09:5B:D2:CF:98:E0:F7:BA:AA:8D:C7:7C:AC:78:F3:97:84:3E:8A:22:A6:6D"
+            }
+        });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn x509_decode_der() {
+        let der = crate::PemDecode::new(None).execute(CERTIFICATE.as_bytes()).unwrap();
+        let from_der = X509Decode::new().execute(&der).unwrap();
+        assert_eq!(from_der, X509Decode::new().execute(CERTIFICATE.as_bytes()).unwrap());
+    }
+
+    #[test]
+    fn x509_decode_errors() {
+        let der = crate::PemDecode::new(None).execute(CERTIFICATE.as_bytes()).unwrap();
+        let decoder = X509Decode::new();
+        assert!(decoder.execute(&der[..der.len() - 1]).is_err());
+        assert!(decoder.execute(&[0x30, 0x03, 0x02, 0x01, 0x01]).is_err());
+        assert!(decoder
+            .execute(b"-----BEGIN PUBLIC KEY-----\nAA==\n-----END PUBLIC KEY-----")
+            .is_err());
+    }
+
+    #[test]
+    fn x509_rsa_key_bits() {
+        let mut modulus = vec![0x00, 0x80];
+        modulus.extend_from_slice(&[0; 255]);
+        assert_eq!(bit_length(&modulus), 2048);
+        assert_eq!(bit_length(&[0x01, 0x00, 0x01]), 17);
+    }
+}
@@ -1,9 +1,17 @@
+use alloc::vec;
+use alloc::vec::Vec;
 use data_encoding::BASE32HEX;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "registry")]
+use serde_json::{json, Value};
 
+use super::{decode_into, encode_into};
 use crate::Operation;
 use crate::OperationError;
+use crate::Reversible;
+#[cfg(feature = "registry")]
+use crate::{Category, OperationInfo};
 
 #[derive(Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -11,7 +19,13 @@ pub struct Base32HexDecode {}
 
 impl Operation for Base32HexDecode {
     fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
-        Ok(BASE32HEX.decode(input)?)
+        let mut output = vec![];
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        decode_into(&BASE32HEX, input, out)
     }
 }
 
@@ -27,7 +41,14 @@ pub struct Base32HexEncode {}
 
 impl Operation for Base32HexEncode {
     fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
-        Ok(BASE32HEX.encode(input).into())
+        let mut output = vec![];
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        encode_into(&BASE32HEX, input, out);
+        Ok(())
     }
 }
 
@@ -37,8 +58,50 @@ impl Base32HexEncode {
     }
 }
 
+impl Reversible for Base32HexDecode {
+    type Inverse = Base32HexEncode;
+
+    fn inverse(&self) -> Base32HexEncode {
+        Base32HexEncode::new()
+    }
+}
+
+impl Reversible for Base32HexEncode {
+    type Inverse = Base32HexDecode;
+
+    fn inverse(&self) -> Base32HexDecode {
+        Base32HexDecode::new()
+    }
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for Base32HexDecode {
+    const ID: &'static str = "base32hex_decode";
+    const NAME: &'static str = "Base32Hex Decode";
+    const CATEGORY: Category = Category::Encoding;
+    const DESCRIPTION: &'static str = "Decode base32 with the extended hex alphabet (RFC 4648)";
+
+    fn options_schema() -> Value {
+        json!({ "type": "object", "properties": {} })
+    }
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for Base32HexEncode {
+    const ID: &'static str = "base32hex_encode";
+    const NAME: &'static str = "Base32Hex Encode";
+    const CATEGORY: Category = Category::Encoding;
+    const DESCRIPTION: &'static str = "Encode base32 with the extended hex alphabet (RFC 4648)";
+
+    fn options_schema() -> Value {
+        json!({ "type": "object", "properties": {} })
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     #[test]
@@ -56,4 +119,13 @@ mod tests {
         let expected = "D5P6URJ7C5Q6A===".as_bytes().to_vec();
         assert_eq!(actual, expected);
     }
+
+    proptest! {
+        #[test]
+        fn base32hex_round_trip(input: Vec<u8>) {
+            let encoder = Base32HexEncode::new();
+            let encoded = encoder.execute(&input).unwrap();
+            prop_assert_eq!(encoder.inverse().execute(&encoded).unwrap(), input);
+        }
+    }
 }
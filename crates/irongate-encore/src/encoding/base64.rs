@@ -1,11 +1,20 @@
-use data_encoding;
+use alloc::vec;
+use alloc::vec::Vec;
+use data_encoding::Encoding;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "registry")]
+use serde_json::{json, Value};
 
+use super::simd::{self, Base64Alphabet};
+use super::{decode_into, encode_into};
 use crate::Operation;
 use crate::OperationError;
+use crate::Reversible;
+#[cfg(feature = "registry")]
+use crate::{Category, OperationInfo};
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Base64Format {
@@ -14,6 +23,25 @@ pub enum Base64Format {
     Mime,
 }
 
+fn encoding(format: Base64Format, pad: bool) -> Encoding {
+    match (format, pad) {
+        (Base64Format::Standard, false) => data_encoding::BASE64_NOPAD,
+        (Base64Format::Standard, true) => data_encoding::BASE64,
+        (Base64Format::Url, false) => data_encoding::BASE64URL_NOPAD,
+        (Base64Format::Url, true) => data_encoding::BASE64URL,
+        (Base64Format::Mime, _) => data_encoding::BASE64_MIME,
+    }
+}
+
+/// MIME output is wrapped into lines, only the plain alphabets have vectorised kernels
+fn simd_alphabet(format: Base64Format) -> Option<&'static Base64Alphabet> {
+    match format {
+        Base64Format::Standard => Some(&Base64Alphabet::STANDARD),
+        Base64Format::Url => Some(&Base64Alphabet::URL),
+        Base64Format::Mime => None,
+    }
+}
+
 #[derive(Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Base64Decode {
@@ -23,13 +51,34 @@ pub struct Base64Decode {
 
 impl Operation for Base64Decode {
     fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
-        Ok(match (&self.format, self.pad) {
-            (Base64Format::Standard, false) => data_encoding::BASE64_NOPAD.decode(input)?,
-            (Base64Format::Standard, true) => data_encoding::BASE64.decode(input)?,
-            (Base64Format::Url, false) => data_encoding::BASE64URL_NOPAD.decode(input)?,
-            (Base64Format::Url, true) => data_encoding::BASE64URL.decode(input)?,
-            (Base64Format::Mime, _) => data_encoding::BASE64_MIME.decode(input)?,
-        })
+        let mut output = vec![];
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        let encoding = encoding(self.format, self.pad);
+        let Some(alphabet) = simd_alphabet(self.format) else {
+            return decode_into(&encoding, input, out);
+        };
+        let start = out.len();
+        out.resize(start + encoding.decode_len(input.len())?, 0);
+        // The kernels stop before padding, data_encoding decodes and validates the tail
+        let consumed = simd::base64_decode(input, &mut out[start..], alphabet);
+        let tail = &input[consumed..];
+        let tail_start = start + consumed / 4 * 3;
+        let tail_len = encoding.decode_len(tail.len())?;
+        match encoding.decode_mut(tail, &mut out[tail_start..tail_start + tail_len]) {
+            Ok(len) => {
+                out.truncate(tail_start + len);
+                Ok(())
+            }
+            Err(_) => {
+                // Decode everything again so the error position covers the whole input
+                out.truncate(start);
+                decode_into(&encoding, input, out)
+            }
+        }
     }
 }
 
@@ -48,14 +97,22 @@ pub struct Base64Encode {
 
 impl Operation for Base64Encode {
     fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
-        let encoded = match (&self.format, self.pad) {
-            (Base64Format::Standard, false) => data_encoding::BASE64_NOPAD.encode(input),
-            (Base64Format::Standard, true) => data_encoding::BASE64.encode(input),
-            (Base64Format::Url, false) => data_encoding::BASE64URL_NOPAD.encode(input),
-            (Base64Format::Url, true) => data_encoding::BASE64URL.encode(input),
-            (Base64Format::Mime, _) => data_encoding::BASE64_MIME.encode(input),
+        let mut output = vec![];
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        let encoding = encoding(self.format, self.pad);
+        let Some(alphabet) = simd_alphabet(self.format) else {
+            encode_into(&encoding, input, out);
+            return Ok(());
         };
-        Ok(encoded.into())
+        let start = out.len();
+        out.resize(start + encoding.encode_len(input.len()), 0);
+        let consumed = simd::base64_encode(input, &mut out[start..], alphabet);
+        encoding.encode_mut(&input[consumed..], &mut out[start + consumed / 3 * 4..]);
+        Ok(())
     }
 }
 
@@ -65,8 +122,72 @@ impl Base64Encode {
     }
 }
 
+impl Reversible for Base64Decode {
+    type Inverse = Base64Encode;
+
+    fn inverse(&self) -> Base64Encode {
+        Base64Encode::new(self.format, self.pad)
+    }
+}
+
+impl Reversible for Base64Encode {
+    type Inverse = Base64Decode;
+
+    fn inverse(&self) -> Base64Decode {
+        Base64Decode::new(self.format, self.pad)
+    }
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for Base64Decode {
+    const ID: &'static str = "base64_decode";
+    const NAME: &'static str = "Base64 Decode";
+    const CATEGORY: Category = Category::Encoding;
+    const DESCRIPTION: &'static str = "Decode standard, URL-safe or MIME base64";
+
+    fn options_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "format": {
+                    "type": "string",
+                    "enum": ["standard", "url", "mime"],
+                    "default": "standard"
+                },
+                "pad": { "type": "boolean", "default": true }
+            },
+            "required": ["format", "pad"]
+        })
+    }
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for Base64Encode {
+    const ID: &'static str = "base64_encode";
+    const NAME: &'static str = "Base64 Encode";
+    const CATEGORY: Category = Category::Encoding;
+    const DESCRIPTION: &'static str = "Encode to standard, URL-safe or MIME base64";
+
+    fn options_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "format": {
+                    "type": "string",
+                    "enum": ["standard", "url", "mime"],
+                    "default": "standard"
+                },
+                "pad": { "type": "boolean", "default": true }
+            },
+            "required": ["format", "pad"]
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     #[test]
@@ -84,4 +205,35 @@ mod tests {
         let expected = "aXJvbmdhdGU=".as_bytes().to_vec();
         assert_eq!(actual, expected);
     }
+
+    fn base64_format() -> impl Strategy<Value = Base64Format> {
+        prop_oneof![Just(Base64Format::Standard), Just(Base64Format::Url), Just(Base64Format::Mime)]
+    }
+
+    proptest! {
+        #[test]
+        fn base64_round_trip(input: Vec<u8>, format in base64_format(), pad: bool) {
+            let encoder = Base64Encode::new(format, pad);
+            let encoded = encoder.execute(&input).unwrap();
+            prop_assert_eq!(encoder.inverse().execute(&encoded).unwrap(), input);
+        }
+
+        #[test]
+        fn base64_decode_inverse_round_trip(input: Vec<u8>, format in base64_format(), pad: bool) {
+            let decoder = Base64Decode::new(format, pad);
+            let encoded = decoder.inverse().execute(&input).unwrap();
+            prop_assert_eq!(decoder.execute(&encoded).unwrap(), input);
+        }
+
+        #[test]
+        fn base64_decode_matches_data_encoding(
+            input in "[A-Za-z0-9+/=_-]{0,200}",
+            format in base64_format(),
+            pad: bool,
+        ) {
+            let decoder = Base64Decode::new(format, pad);
+            let expected = encoding(format, pad).decode(input.as_bytes()).ok();
+            prop_assert_eq!(decoder.execute(input.as_bytes()).ok(), expected);
+        }
+    }
 }
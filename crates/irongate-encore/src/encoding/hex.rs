@@ -1,10 +1,17 @@
-use bstr::ByteSlice;
-use hex;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "registry")]
+use serde_json::{json, Value};
 
+use super::simd;
 use crate::Operation;
 use crate::OperationError;
+use crate::Reversible;
+#[cfg(feature = "registry")]
+use crate::{Category, OperationInfo};
 
 #[derive(Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -13,23 +20,92 @@ pub struct HexDecode {
     delimiter: Option<String>,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum HexFormat {
     Upper,
     Lower,
 }
 
+const LOWER: &[u8; 16] = b"0123456789abcdef";
+const UPPER: &[u8; 16] = b"0123456789ABCDEF";
+
+fn non_empty(separator: &Option<String>) -> Option<&[u8]> {
+    separator.as_deref().map(str::as_bytes).filter(|s| !s.is_empty())
+}
+
+pub(crate) fn nibble(digit: u8) -> Option<u8> {
+    match digit {
+        b'0'..=b'9' => Some(digit - b'0'),
+        b'a'..=b'f' => Some(digit - b'a' + 10),
+        b'A'..=b'F' => Some(digit - b'A' + 10),
+        _ => None,
+    }
+}
+
+fn invalid_hex(offset: usize) -> OperationError {
+    OperationError::DecodeError { message: "Invalid Hex input".to_string(), offset: Some(offset) }
+}
+
+/// First byte from `from` on that is not a hex digit, or else the unpaired last digit
+fn invalid_digit(input: &[u8], from: usize) -> usize {
+    input[from..]
+        .iter()
+        .position(|&digit| nibble(digit).is_none())
+        .map_or(input.len() - 1, |position| from + position)
+}
+
 impl Operation for HexDecode {
     fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
-        let mut input = input.to_vec();
-        if let Some(p) = &self.prefix {
-            input = input.replace(p, "");
-        };
-        if let Some(d) = &self.delimiter {
-            input = input.replace(d, "");
-        };
-        Ok(hex::decode(input)?)
+        let mut output = vec![];
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        let prefix = non_empty(&self.prefix);
+        let delimiter = non_empty(&self.delimiter);
+        let start = out.len();
+        if prefix.is_none() && delimiter.is_none() {
+            let mut valid = 0;
+            if input.len() % 2 == 0 {
+                out.resize(start + input.len() / 2, 0);
+                valid = simd::hex_decode(input, &mut out[start..]);
+                if valid == input.len() {
+                    return Ok(());
+                }
+            }
+            out.truncate(start);
+            // The valid prefix ends on a pair, the invalid digit is one of the next two
+            return Err(invalid_hex(invalid_digit(input, valid)));
+        }
+        out.reserve(input.len() / 2);
+
+        let mut high = None;
+        let mut i = 0;
+        while i < input.len() {
+            let rest = &input[i..];
+            if let Some(skip) =
+                [prefix, delimiter].into_iter().flatten().find(|s| rest.starts_with(s))
+            {
+                i += skip.len();
+                continue;
+            }
+            let Some(low) = nibble(input[i]) else {
+                out.truncate(start);
+                return Err(invalid_hex(i));
+            };
+            match high.take() {
+                Some((high, _)) => out.push(high << 4 | low),
+                None => high = Some((low, i)),
+            }
+            i += 1;
+        }
+        if let Some((_, unpaired)) = high {
+            out.truncate(start);
+            return Err(invalid_hex(unpaired));
+        }
+        Ok(())
     }
 }
 
@@ -49,27 +125,33 @@ pub struct HexEncode {
 
 impl Operation for HexEncode {
     fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
-        let hex_string = match self.format {
-            HexFormat::Lower => hex::encode(input),
-            HexFormat::Upper => hex::encode_upper(input),
-        };
         let mut output = vec![];
-        let delimiter =
-            self.delimiter.clone().and_then(|d| if d.is_empty() { None } else { Some(d) });
-        let prefix = self.prefix.clone().and_then(|p| if p.is_empty() { None } else { Some(p) });
-        let mut chunks = hex_string.as_bytes().chunks(2).peekable();
-        while let Some(chunk) = chunks.next() {
-            if let Some(p) = &prefix {
-                output.extend_from_slice(p.as_bytes());
-            }
-            output.extend_from_slice(chunk);
-            if let Some(d) = &delimiter {
-                if chunks.peek().is_some() {
-                    output.extend_from_slice(d.as_bytes());
-                }
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        let table = match self.format {
+            HexFormat::Lower => LOWER,
+            HexFormat::Upper => UPPER,
+        };
+        let prefix = non_empty(&self.prefix).unwrap_or_default();
+        let delimiter = non_empty(&self.delimiter).unwrap_or_default();
+        if prefix.is_empty() && delimiter.is_empty() {
+            let start = out.len();
+            out.resize(start + input.len() * 2, 0);
+            simd::hex_encode(input, &mut out[start..], table);
+            return Ok(());
+        }
+        out.reserve(input.len() * (2 + prefix.len() + delimiter.len()));
+        for (i, byte) in input.iter().enumerate() {
+            if i > 0 {
+                out.extend_from_slice(delimiter);
             }
+            out.extend_from_slice(prefix);
+            out.extend_from_slice(&[table[usize::from(byte >> 4)], table[usize::from(byte & 0xf)]]);
         }
-        Ok(output)
+        Ok(())
     }
 }
 
@@ -79,8 +161,67 @@ impl HexEncode {
     }
 }
 
+/// The inverse encodes lowercase, round trips need a prefix and delimiter that
+/// cannot be confused with hex digits or with each other
+impl Reversible for HexDecode {
+    type Inverse = HexEncode;
+
+    fn inverse(&self) -> HexEncode {
+        HexEncode::new(HexFormat::Lower, self.prefix.clone(), self.delimiter.clone())
+    }
+}
+
+impl Reversible for HexEncode {
+    type Inverse = HexDecode;
+
+    fn inverse(&self) -> HexDecode {
+        HexDecode::new(self.prefix.clone(), self.delimiter.clone())
+    }
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for HexDecode {
+    const ID: &'static str = "hex_decode";
+    const NAME: &'static str = "Hex Decode";
+    const CATEGORY: Category = Category::Encoding;
+    const DESCRIPTION: &'static str = "Decode hexadecimal, ignoring a byte prefix and delimiter";
+
+    fn options_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "prefix": { "type": ["string", "null"] },
+                "delimiter": { "type": ["string", "null"] }
+            }
+        })
+    }
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for HexEncode {
+    const ID: &'static str = "hex_encode";
+    const NAME: &'static str = "Hex Encode";
+    const CATEGORY: Category = Category::Encoding;
+    const DESCRIPTION: &'static str =
+        "Encode to hexadecimal with an optional byte prefix and delimiter";
+
+    fn options_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "format": { "type": "string", "enum": ["Upper", "Lower"], "default": "Lower" },
+                "prefix": { "type": ["string", "null"] },
+                "delimiter": { "type": ["string", "null"] }
+            },
+            "required": ["format"]
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     #[test]
@@ -108,6 +249,42 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn hex_decode_into_appends() {
+        let decoder = HexDecode::new(Some("0x".to_string()), Some(" ".to_string()));
+        let mut output = b"iron".to_vec();
+        decoder.execute_into(b"0x67 0x61 0x74 0x65", &mut output).unwrap();
+        assert_eq!(output, b"irongate");
+    }
+
+    #[test]
+    fn hex_decode_into_keeps_output_on_error() {
+        let decoder = HexDecode::new(None, None);
+        let mut output = b"iron".to_vec();
+        assert!(decoder.execute_into(b"6761746", &mut output).is_err());
+        assert!(decoder.execute_into(b"676174zz", &mut output).is_err());
+        assert_eq!(output, b"iron");
+    }
+
+    #[test]
+    fn hex_decode_error_offset() {
+        let offset = |decoder: &HexDecode, input: &[u8]| match decoder.execute(input) {
+            Err(OperationError::DecodeError { offset, .. }) => offset,
+            _ => panic!("expected a decode error"),
+        };
+        let decoder = HexDecode::new(None, None);
+        // Past the first SIMD block
+        let long = [b"69".repeat(40), b"7z".to_vec()].concat();
+        assert_eq!(offset(&decoder, &long), Some(81));
+        assert_eq!(offset(&decoder, b"6z72"), Some(1));
+        assert_eq!(offset(&decoder, b"z672"), Some(0));
+        assert_eq!(offset(&decoder, b"697"), Some(2));
+        assert_eq!(offset(&decoder, b"6z7"), Some(1));
+        let decoder = HexDecode::new(Some("0x".to_string()), Some(" ".to_string()));
+        assert_eq!(offset(&decoder, b"0x69 0xzz"), Some(7));
+        assert_eq!(offset(&decoder, b"0x69 0x7"), Some(7));
+    }
+
     #[test]
     fn hex_encode_prefix_upper() {
         let encoder = HexEncode::new(HexFormat::Upper, Some("\\x".to_string()), None);
@@ -139,4 +316,46 @@ mod tests {
         let expected = "69\n72\n6F\n6E\n67\n61\n74\n65".as_bytes().to_vec();
         assert_eq!(actual, expected);
     }
+
+    fn hex_format() -> impl Strategy<Value = HexFormat> {
+        prop_oneof![Just(HexFormat::Upper), Just(HexFormat::Lower)]
+    }
+
+    fn separator(choices: Vec<&'static str>) -> impl Strategy<Value = Option<String>> {
+        prop::option::of(prop::sample::select(choices))
+            .prop_map(|separator| separator.map(str::to_string))
+    }
+
+    fn prefix() -> impl Strategy<Value = Option<String>> {
+        separator(vec!["", "0x", "\\x", "%"])
+    }
+
+    fn delimiter() -> impl Strategy<Value = Option<String>> {
+        separator(vec!["", ",", ", ", "\n", ":"])
+    }
+
+    proptest! {
+        #[test]
+        fn hex_round_trip(
+            input: Vec<u8>,
+            format in hex_format(),
+            prefix in prefix(),
+            delimiter in delimiter(),
+        ) {
+            let encoder = HexEncode::new(format, prefix, delimiter);
+            let encoded = encoder.execute(&input).unwrap();
+            prop_assert_eq!(encoder.inverse().execute(&encoded).unwrap(), input);
+        }
+
+        #[test]
+        fn hex_decode_inverse_round_trip(
+            input: Vec<u8>,
+            prefix in prefix(),
+            delimiter in delimiter(),
+        ) {
+            let decoder = HexDecode::new(prefix, delimiter);
+            let encoded = decoder.inverse().execute(&input).unwrap();
+            prop_assert_eq!(decoder.execute(&encoded).unwrap(), input);
+        }
+    }
 }
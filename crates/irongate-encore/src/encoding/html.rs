@@ -1,8 +1,15 @@
+use alloc::vec;
+use alloc::vec::Vec;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "registry")]
+use serde_json::{json, Value};
 
 use crate::Operation;
 use crate::OperationError;
+use crate::Reversible;
+#[cfg(feature = "registry")]
+use crate::{Category, OperationInfo};
 
 #[derive(Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -12,18 +19,24 @@ pub struct HtmlDecode {}
 // and this http://stackoverflow.com/questions/7381974
 impl Operation for HtmlDecode {
     fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
-        let mut decoded = vec![];
+        let mut output = vec![];
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        out.reserve(input.len());
         let mut i = 0;
         while i < input.len() {
             if let Some(window) = input.get(i..i + 4) {
                 match window {
                     b"&gt;" => {
-                        decoded.push(b'>');
+                        out.push(b'>');
                         i += 4;
                         continue;
                     }
                     b"&lt;" => {
-                        decoded.push(b'<');
+                        out.push(b'<');
                         i += 4;
                         continue;
                     }
@@ -33,12 +46,12 @@ impl Operation for HtmlDecode {
             if let Some(window) = input.get(i..i + 5) {
                 match window {
                     b"&amp;" => {
-                        decoded.push(b'&');
+                        out.push(b'&');
                         i += 5;
                         continue;
                     }
                     b"&#39;" => {
-                        decoded.push(b'\\');
+                        out.push(b'\\');
                         i += 5;
                         continue;
                     }
@@ -48,15 +61,15 @@ impl Operation for HtmlDecode {
             }
             if let Some(window) = input.get(i..i + 6) {
                 if window == b"&quot;" {
-                    decoded.push(b'"');
+                    out.push(b'"');
                     i += 6;
                     continue;
                 }
             }
-            decoded.push(input[i]);
+            out.push(input[i]);
             i += 1;
         }
-        Ok(decoded)
+        Ok(())
     }
 }
 
@@ -72,18 +85,24 @@ pub struct HtmlEncode {}
 
 impl Operation for HtmlEncode {
     fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
-        let mut encoded = vec![];
+        let mut output = vec![];
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        out.reserve(input.len());
         for ch in input {
             match ch {
-                b'>' => encoded.extend_from_slice(b"&gt;"),
-                b'<' => encoded.extend_from_slice(b"&lt;"),
-                b'&' => encoded.extend_from_slice(b"&amp;"),
-                b'\\' => encoded.extend_from_slice(b"&#39;"),
-                b'"' => encoded.extend_from_slice(b"&quot;"),
-                byte => encoded.push(*byte),
+                b'>' => out.extend_from_slice(b"&gt;"),
+                b'<' => out.extend_from_slice(b"&lt;"),
+                b'&' => out.extend_from_slice(b"&amp;"),
+                b'\\' => out.extend_from_slice(b"&#39;"),
+                b'"' => out.extend_from_slice(b"&quot;"),
+                byte => out.push(*byte),
             };
         }
-        Ok(encoded)
+        Ok(())
     }
 }
 
@@ -93,8 +112,50 @@ impl HtmlEncode {
     }
 }
 
+impl Reversible for HtmlDecode {
+    type Inverse = HtmlEncode;
+
+    fn inverse(&self) -> HtmlEncode {
+        HtmlEncode::new()
+    }
+}
+
+impl Reversible for HtmlEncode {
+    type Inverse = HtmlDecode;
+
+    fn inverse(&self) -> HtmlDecode {
+        HtmlDecode::new()
+    }
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for HtmlDecode {
+    const ID: &'static str = "html_decode";
+    const NAME: &'static str = "HTML Decode";
+    const CATEGORY: Category = Category::Encoding;
+    const DESCRIPTION: &'static str = "Decode the HTML entities produced by HTML Encode";
+
+    fn options_schema() -> Value {
+        json!({ "type": "object", "properties": {} })
+    }
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for HtmlEncode {
+    const ID: &'static str = "html_encode";
+    const NAME: &'static str = "HTML Encode";
+    const CATEGORY: Category = Category::Encoding;
+    const DESCRIPTION: &'static str = "Escape HTML special characters as entities";
+
+    fn options_schema() -> Value {
+        json!({ "type": "object", "properties": {} })
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     #[test]
@@ -113,4 +174,20 @@ mod tests {
         let expected = b"&#39;&amp;&lt;script&gt;alert(1)&lt;/script&gt;a&quot;".to_vec();
         assert_eq!(actual, expected);
     }
+
+    proptest! {
+        #[test]
+        fn html_round_trip(input: Vec<u8>) {
+            let encoder = HtmlEncode::new();
+            let encoded = encoder.execute(&input).unwrap();
+            prop_assert_eq!(encoder.inverse().execute(&encoded).unwrap(), input);
+        }
+
+        #[test]
+        fn html_round_trip_markup(input in "[<>&\"\\\\a-z;#0-9 ]*") {
+            let encoder = HtmlEncode::new();
+            let encoded = encoder.execute(input.as_bytes()).unwrap();
+            prop_assert_eq!(encoder.inverse().execute(&encoded).unwrap(), input.as_bytes());
+        }
+    }
 }
@@ -1,5 +1,38 @@
+use alloc::vec::Vec;
+use data_encoding::Encoding;
+
+use crate::OperationError;
+
 pub mod base32hex;
 pub mod base64;
 pub mod hex;
 pub mod html;
+pub mod pem;
 pub mod url;
+
+mod simd;
+
+pub(crate) fn encode_into(encoding: &Encoding, input: &[u8], out: &mut Vec<u8>) {
+    let start = out.len();
+    out.resize(start + encoding.encode_len(input.len()), 0);
+    encoding.encode_mut(input, &mut out[start..]);
+}
+
+pub(crate) fn decode_into(
+    encoding: &Encoding,
+    input: &[u8],
+    out: &mut Vec<u8>,
+) -> Result<(), OperationError> {
+    let start = out.len();
+    out.resize(start + encoding.decode_len(input.len())?, 0);
+    match encoding.decode_mut(input, &mut out[start..]) {
+        Ok(len) => {
+            out.truncate(start + len);
+            Ok(())
+        }
+        Err(partial) => {
+            out.truncate(start);
+            Err(partial.error.into())
+        }
+    }
+}
@@ -0,0 +1,234 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use bstr::ByteSlice;
+use data_encoding::BASE64;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "registry")]
+use serde_json::{json, Value};
+
+use super::decode_into;
+use crate::Operation;
+use crate::OperationError;
+use crate::Reversible;
+#[cfg(feature = "registry")]
+use crate::{Category, OperationInfo};
+
+const LINE_WIDTH: usize = 64;
+
+/// Used by the certificate decoders to accept PEM as well as DER
+#[cfg(feature = "certificate")]
+pub(crate) fn is_pem(input: &[u8]) -> bool {
+    input.trim_start().starts_with(b"-----BEGIN ")
+}
+
+/// Decode the body of the first PEM block, restricted to blocks with the given label
+pub(crate) fn decode_block(
+    input: &[u8],
+    label: Option<&str>,
+    out: &mut Vec<u8>,
+) -> Result<(), OperationError> {
+    let mut rest = input;
+    loop {
+        let Some(begin) = rest.find(b"-----BEGIN ") else {
+            return Err(OperationError::DecodeError {
+                message: "No PEM block found".into(),
+                offset: None,
+            });
+        };
+        rest = &rest[begin + 11..];
+        let Some(label_end) = rest.find(b"-----") else {
+            return Err(OperationError::DecodeError {
+                message: "Invalid PEM header".into(),
+                offset: None,
+            });
+        };
+        let found = &rest[..label_end];
+        rest = &rest[label_end + 5..];
+        if label.is_some_and(|label| label.as_bytes() != found) {
+            continue;
+        }
+
+        let footer = [b"-----END ", found, b"-----"].concat();
+        let Some(end) = rest.find(&footer) else {
+            return Err(OperationError::DecodeError {
+                message: "Missing PEM footer".into(),
+                offset: None,
+            });
+        };
+        // Lines holding a colon are RFC 1421 headers such as `Proc-Type`
+        let body: Vec<u8> = rest[..end]
+            .lines()
+            .filter(|line| !line.contains(&b':'))
+            .flat_map(|line| line.iter().copied().filter(|byte| !byte.is_ascii_whitespace()))
+            .collect();
+        // Positions in the body without its headers and whitespace would mislead
+        return decode_into(&BASE64, &body, out).map_err(|err| match err {
+            OperationError::DecodeError { message, .. } => {
+                OperationError::DecodeError { message, offset: None }
+            }
+            err => err,
+        });
+    }
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PemDecode {
+    label: Option<String>,
+}
+
+impl Operation for PemDecode {
+    fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
+        let mut output = vec![];
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        let label = self.label.as_deref().filter(|label| !label.is_empty());
+        decode_block(input, label, out)
+    }
+}
+
+impl PemDecode {
+    pub const fn new(label: Option<String>) -> Self {
+        PemDecode { label }
+    }
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PemEncode {
+    label: String,
+}
+
+impl Operation for PemEncode {
+    fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
+        let mut output = vec![];
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        let encoded = BASE64.encode(input);
+        out.reserve(encoded.len() + encoded.len() / LINE_WIDTH + 2 * self.label.len() + 32);
+        out.extend_from_slice(b"-----BEGIN ");
+        out.extend_from_slice(self.label.as_bytes());
+        out.extend_from_slice(b"-----\n");
+        for line in encoded.as_bytes().chunks(LINE_WIDTH) {
+            out.extend_from_slice(line);
+            out.push(b'\n');
+        }
+        out.extend_from_slice(b"-----END ");
+        out.extend_from_slice(self.label.as_bytes());
+        out.extend_from_slice(b"-----\n");
+        Ok(())
+    }
+}
+
+impl PemEncode {
+    pub const fn new(label: String) -> Self {
+        PemEncode { label }
+    }
+}
+
+impl Reversible for PemEncode {
+    type Inverse = PemDecode;
+
+    fn inverse(&self) -> PemDecode {
+        PemDecode::new(Some(self.label.clone()))
+    }
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for PemDecode {
+    const ID: &'static str = "pem_decode";
+    const NAME: &'static str = "PEM Decode";
+    const CATEGORY: Category = Category::Encoding;
+    const DESCRIPTION: &'static str = "Decode the first PEM block, optionally matching a label";
+
+    fn options_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "label": { "type": ["string", "null"] }
+            }
+        })
+    }
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for PemEncode {
+    const ID: &'static str = "pem_encode";
+    const NAME: &'static str = "PEM Encode";
+    const CATEGORY: Category = Category::Encoding;
+    const DESCRIPTION: &'static str = "Wrap data into a PEM block with the given label";
+
+    fn options_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "label": { "type": "string", "default": "CERTIFICATE" }
+            },
+            "required": ["label"]
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn pem_encode() {
+        let encoder = PemEncode::new("DATA".to_string());
+        let actual = encoder.execute(&[b'x'; 50]).unwrap();
+        let expected = "-----BEGIN DATA-----\n\
+            eHh4eHh4eHh4eHh4eHh4eHh4eHh4eHh4eHh4eHh4eHh4eHh4eHh4eHh4eHh4eHh4\n\
+            eHg=\n\
+            -----END DATA-----\n";
+        assert_eq!(actual, expected.as_bytes());
+    }
+
+    #[test]
+    fn pem_decode_label() {
+        let input =
+            "-----BEGIN EC PARAMETERS-----\nBggqhkjOPQMBBw==\n-----END EC PARAMETERS-----\n\
+            -----BEGIN DATA-----\naXJvbmdhdGU=\n-----END DATA-----\n";
+        assert_eq!(PemDecode::new(None).execute(input.as_bytes()).unwrap()[0], 0x06);
+        let decoder = PemDecode::new(Some("DATA".to_string()));
+        assert_eq!(decoder.execute(input.as_bytes()).unwrap(), b"irongate");
+    }
+
+    #[test]
+    fn pem_decode_skips_headers() {
+        let input = "-----BEGIN DATA-----\r\nProc-Type: 4,ENCRYPTED\r\n\r\naXJv\r\nbmdhdGU=\r\n\
+            -----END DATA-----";
+        assert_eq!(PemDecode::new(None).execute(input.as_bytes()).unwrap(), b"irongate");
+    }
+
+    #[test]
+    fn pem_decode_errors() {
+        let decoder = PemDecode::new(Some("CERTIFICATE".to_string()));
+        assert!(decoder
+            .execute(b"-----BEGIN DATA-----\naXJvbmdhdGU=\n-----END DATA-----")
+            .is_err());
+        assert!(decoder.execute(b"-----BEGIN CERTIFICATE-----\naXJvbmdhdGU=\n").is_err());
+        assert!(decoder
+            .execute(b"-----BEGIN CERTIFICATE-----\n!!\n-----END CERTIFICATE-----")
+            .is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn pem_round_trip(input: Vec<u8>, label in "[A-Z][A-Z ]{0,20}[A-Z]") {
+            let encoder = PemEncode::new(label);
+            let encoded = encoder.execute(&input).unwrap();
+            prop_assert_eq!(encoder.inverse().execute(&encoded).unwrap(), input);
+        }
+    }
+}
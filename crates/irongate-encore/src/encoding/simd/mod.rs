@@ -0,0 +1,382 @@
+//! Vectorised kernels for the hex and base64 codecs.
+//!
+//! The instruction set is picked at runtime, every kernel handles the whole blocks it can
+//! and the scalar kernel finishes the remainder so the output is identical on every target.
+//! Decoders stop at the first pair or quad holding a foreign symbol and return how much input
+//! they consumed, callers report the error (or handle base64 padding) from there.
+
+mod scalar;
+
+#[cfg(target_arch = "aarch64")]
+mod neon;
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+mod wasm;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod x86;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Level {
+    // simd128 is a compile time feature on wasm, the scalar kernel is then never selected
+    #[cfg_attr(all(target_arch = "wasm32", target_feature = "simd128"), allow(dead_code))]
+    Scalar,
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    Ssse3,
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    Avx2,
+    #[cfg(target_arch = "aarch64")]
+    Neon,
+    #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+    Simd128,
+}
+
+impl Level {
+    /// Without `std` there is no runtime detection, the level is then picked from the
+    /// target features enabled at compile time
+    pub(crate) fn detect() -> Level {
+        #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "std"))]
+        {
+            if std::is_x86_feature_detected!("avx2") {
+                return Level::Avx2;
+            }
+            if std::is_x86_feature_detected!("ssse3") {
+                return Level::Ssse3;
+            }
+        }
+        #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), not(feature = "std")))]
+        {
+            if cfg!(target_feature = "avx2") {
+                return Level::Avx2;
+            }
+            if cfg!(target_feature = "ssse3") {
+                return Level::Ssse3;
+            }
+        }
+        #[cfg(all(target_arch = "aarch64", feature = "std"))]
+        {
+            if std::arch::is_aarch64_feature_detected!("neon") {
+                return Level::Neon;
+            }
+        }
+        #[cfg(all(target_arch = "aarch64", not(feature = "std")))]
+        {
+            if cfg!(target_feature = "neon") {
+                return Level::Neon;
+            }
+        }
+        #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+        return Level::Simd128;
+        #[cfg(not(all(target_arch = "wasm32", target_feature = "simd128")))]
+        return Level::Scalar;
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Base64Alphabet {
+    symbols: &'static [u8; 64],
+    values: [u8; 256],
+}
+
+impl Base64Alphabet {
+    pub(crate) const STANDARD: Base64Alphabet =
+        Base64Alphabet::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/");
+    pub(crate) const URL: Base64Alphabet =
+        Base64Alphabet::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_");
+
+    /// Foreign symbols map to `INVALID` in the reverse table
+    const INVALID: u8 = 0xff;
+
+    const fn new(symbols: &'static [u8; 64]) -> Self {
+        let mut values = [Self::INVALID; 256];
+        let mut i = 0;
+        while i < symbols.len() {
+            values[symbols[i] as usize] = i as u8;
+            i += 1;
+        }
+        Base64Alphabet { symbols, values }
+    }
+
+    #[cfg(any(
+        target_arch = "x86",
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        all(target_arch = "wasm32", target_feature = "simd128")
+    ))]
+    fn ch62(&self) -> u8 {
+        self.symbols[62]
+    }
+
+    #[cfg(any(
+        target_arch = "x86",
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        all(target_arch = "wasm32", target_feature = "simd128")
+    ))]
+    fn ch63(&self) -> u8 {
+        self.symbols[63]
+    }
+
+    /// Offsets added to a 6-bit index to get its symbol, selected by the index range
+    /// (see Wojciech Muła, "Base64 encoding with SIMD instructions")
+    #[cfg(any(
+        target_arch = "x86",
+        target_arch = "x86_64",
+        all(target_arch = "wasm32", target_feature = "simd128")
+    ))]
+    fn shift_lut(&self) -> [u8; 16] {
+        let digits = b'0'.wrapping_sub(52);
+        [
+            b'a'.wrapping_sub(26),
+            digits,
+            digits,
+            digits,
+            digits,
+            digits,
+            digits,
+            digits,
+            digits,
+            digits,
+            digits,
+            self.ch62().wrapping_sub(62),
+            self.ch63().wrapping_sub(63),
+            b'A',
+            0,
+            0,
+        ]
+    }
+}
+
+/// Encode `input` into `out`, which must hold `2 * input.len()` bytes
+pub(crate) fn hex_encode(input: &[u8], out: &mut [u8], table: &[u8; 16]) {
+    hex_encode_with(Level::detect(), input, out, table)
+}
+
+/// Decode the pairs of `input` into `out`, which must hold `input.len() / 2` bytes, and
+/// return the length of the valid prefix
+pub(crate) fn hex_decode(input: &[u8], out: &mut [u8]) -> usize {
+    hex_decode_with(Level::detect(), input, out)
+}
+
+/// Encode the whole 3 byte groups of `input` without padding into `out`, which must hold
+/// `input.len() / 3 * 4` bytes, and return the number of bytes encoded
+pub(crate) fn base64_encode(input: &[u8], out: &mut [u8], alphabet: &Base64Alphabet) -> usize {
+    base64_encode_with(Level::detect(), input, out, alphabet)
+}
+
+/// Decode the leading quads of `input` made of alphabet symbols into `out`, which must hold
+/// `input.len() / 4 * 3` bytes, and return the number of symbols decoded
+pub(crate) fn base64_decode(input: &[u8], out: &mut [u8], alphabet: &Base64Alphabet) -> usize {
+    base64_decode_with(Level::detect(), input, out, alphabet)
+}
+
+fn hex_encode_with(level: Level, input: &[u8], out: &mut [u8], table: &[u8; 16]) {
+    assert!(out.len() >= input.len() * 2);
+    // SAFETY: `level` comes from `Level::detect` so the instruction set is available, the
+    // kernels only touch `input` and `out` within the lengths checked above
+    let consumed = match level {
+        Level::Scalar => 0,
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        Level::Ssse3 => unsafe { x86::hex_encode_ssse3(input, out, table) },
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        Level::Avx2 => unsafe { x86::hex_encode_avx2(input, out, table) },
+        #[cfg(target_arch = "aarch64")]
+        Level::Neon => unsafe { neon::hex_encode(input, out, table) },
+        #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+        Level::Simd128 => unsafe { wasm::hex_encode(input, out, table) },
+    };
+    scalar::hex_encode(&input[consumed..], &mut out[consumed * 2..], table);
+}
+
+fn hex_decode_with(level: Level, input: &[u8], out: &mut [u8]) -> usize {
+    assert!(out.len() >= input.len() / 2);
+    // SAFETY: see `hex_encode_with`
+    let consumed = match level {
+        Level::Scalar => 0,
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        Level::Ssse3 => unsafe { x86::hex_decode_ssse3(input, out) },
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        Level::Avx2 => unsafe { x86::hex_decode_avx2(input, out) },
+        #[cfg(target_arch = "aarch64")]
+        Level::Neon => unsafe { neon::hex_decode(input, out) },
+        #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+        Level::Simd128 => unsafe { wasm::hex_decode(input, out) },
+    };
+    consumed + scalar::hex_decode(&input[consumed..], &mut out[consumed / 2..])
+}
+
+fn base64_encode_with(
+    level: Level,
+    input: &[u8],
+    out: &mut [u8],
+    alphabet: &Base64Alphabet,
+) -> usize {
+    assert!(out.len() >= input.len() / 3 * 4);
+    // SAFETY: see `hex_encode_with`
+    let consumed = match level {
+        Level::Scalar => 0,
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        Level::Ssse3 => unsafe { x86::base64_encode_ssse3(input, out, alphabet) },
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        Level::Avx2 => unsafe { x86::base64_encode_avx2(input, out, alphabet) },
+        #[cfg(target_arch = "aarch64")]
+        Level::Neon => unsafe { neon::base64_encode(input, out, alphabet) },
+        #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+        Level::Simd128 => unsafe { wasm::base64_encode(input, out, alphabet) },
+    };
+    consumed + scalar::base64_encode(&input[consumed..], &mut out[consumed / 3 * 4..], alphabet)
+}
+
+fn base64_decode_with(
+    level: Level,
+    input: &[u8],
+    out: &mut [u8],
+    alphabet: &Base64Alphabet,
+) -> usize {
+    assert!(out.len() >= input.len() / 4 * 3);
+    // SAFETY: see `hex_encode_with`
+    let consumed = match level {
+        Level::Scalar => 0,
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        Level::Ssse3 => unsafe { x86::base64_decode_ssse3(input, out, alphabet) },
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        Level::Avx2 => unsafe { x86::base64_decode_avx2(input, out, alphabet) },
+        #[cfg(target_arch = "aarch64")]
+        Level::Neon => unsafe { neon::base64_decode(input, out, alphabet) },
+        #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+        Level::Simd128 => unsafe { wasm::base64_decode(input, out, alphabet) },
+    };
+    consumed + scalar::base64_decode(&input[consumed..], &mut out[consumed / 4 * 3..], alphabet)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    const LOWER: &[u8; 16] = b"0123456789abcdef";
+    const UPPER: &[u8; 16] = b"0123456789ABCDEF";
+
+    fn levels() -> Vec<Level> {
+        #[allow(unused_mut)]
+        let mut levels = vec![Level::Scalar];
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("ssse3") {
+                levels.push(Level::Ssse3);
+            }
+            if is_x86_feature_detected!("avx2") {
+                levels.push(Level::Avx2);
+            }
+        }
+        #[cfg(target_arch = "aarch64")]
+        {
+            if std::arch::is_aarch64_feature_detected!("neon") {
+                levels.push(Level::Neon);
+            }
+        }
+        #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+        levels.push(Level::Simd128);
+        levels
+    }
+
+    fn bytes() -> impl Strategy<Value = Vec<u8>> {
+        prop::collection::vec(any::<u8>(), 0..512)
+    }
+
+    fn base64_alphabet() -> impl Strategy<Value = (&'static Base64Alphabet, data_encoding::Encoding)>
+    {
+        prop_oneof![
+            Just((&Base64Alphabet::STANDARD, data_encoding::BASE64_NOPAD)),
+            Just((&Base64Alphabet::URL, data_encoding::BASE64URL_NOPAD)),
+        ]
+    }
+
+    #[test]
+    fn detected_level_is_tested() {
+        assert!(levels().contains(&Level::detect()));
+    }
+
+    proptest! {
+        #[test]
+        fn hex_encode_matches_scalar(input in bytes(), upper: bool) {
+            let (table, expected) =
+                if upper { (UPPER, hex::encode_upper(&input)) } else { (LOWER, hex::encode(&input)) };
+            for level in levels() {
+                let mut out = vec![0; input.len() * 2];
+                hex_encode_with(level, &input, &mut out, table);
+                prop_assert_eq!(&out, expected.as_bytes(), "{:?}", level);
+            }
+        }
+
+        #[test]
+        fn hex_decode_matches_scalar(input in bytes(), upper: bool) {
+            let encoded = if upper { hex::encode_upper(&input) } else { hex::encode(&input) };
+            for level in levels() {
+                let mut out = vec![0; input.len()];
+                let consumed = hex_decode_with(level, encoded.as_bytes(), &mut out);
+                prop_assert_eq!(consumed, encoded.len(), "{:?}", level);
+                prop_assert_eq!(&out, &input, "{:?}", level);
+            }
+        }
+
+        #[test]
+        fn hex_decode_stops_at_invalid(
+            input in bytes(),
+            position: prop::sample::Index,
+            invalid in "[^0-9a-fA-F]",
+        ) {
+            let mut encoded = hex::encode(&input).into_bytes();
+            prop_assume!(!encoded.is_empty());
+            let position = position.index(encoded.len());
+            encoded[position] = invalid.as_bytes()[0];
+            for level in levels() {
+                let mut out = vec![0; input.len()];
+                let consumed = hex_decode_with(level, &encoded, &mut out);
+                prop_assert_eq!(consumed, position / 2 * 2, "{:?}", level);
+                prop_assert_eq!(&out[..consumed / 2], &input[..consumed / 2], "{:?}", level);
+            }
+        }
+
+        #[test]
+        fn base64_encode_matches_scalar(input in bytes(), (alphabet, encoding) in base64_alphabet()) {
+            let expected = encoding.encode(&input);
+            for level in levels() {
+                let mut out = vec![0; input.len() / 3 * 4];
+                let consumed = base64_encode_with(level, &input, &mut out, alphabet);
+                prop_assert_eq!(consumed, input.len() / 3 * 3, "{:?}", level);
+                prop_assert_eq!(&out, &expected.as_bytes()[..out.len()], "{:?}", level);
+            }
+        }
+
+        #[test]
+        fn base64_decode_matches_scalar(input in bytes(), (alphabet, encoding) in base64_alphabet()) {
+            let encoded = encoding.encode(&input);
+            for level in levels() {
+                let mut out = vec![0; encoded.len() / 4 * 3];
+                let consumed = base64_decode_with(level, encoded.as_bytes(), &mut out, alphabet);
+                prop_assert_eq!(consumed, encoded.len() / 4 * 4, "{:?}", level);
+                prop_assert_eq!(&out, &input[..out.len()], "{:?}", level);
+            }
+        }
+
+        #[test]
+        fn base64_decode_stops_at_invalid(
+            input in bytes(),
+            (alphabet, encoding) in base64_alphabet(),
+            position: prop::sample::Index,
+            invalid: u8,
+        ) {
+            let mut encoded = encoding.encode(&input).into_bytes();
+            prop_assume!(encoded.len() >= 4);
+            let position = position.index(encoded.len() / 4 * 4);
+            prop_assume!(alphabet.values[usize::from(invalid)] == Base64Alphabet::INVALID);
+            encoded[position] = invalid;
+            for level in levels() {
+                let mut out = vec![0; encoded.len() / 4 * 3];
+                let consumed = base64_decode_with(level, &encoded, &mut out, alphabet);
+                prop_assert_eq!(consumed, position / 4 * 4, "{:?}", level);
+                prop_assert_eq!(&out[..consumed / 4 * 3], &input[..consumed / 4 * 3], "{:?}", level);
+            }
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use core::arch::aarch64::*;
+
+use super::Base64Alphabet;
+
+#[target_feature(enable = "neon")]
+pub(super) unsafe fn hex_encode(input: &[u8], out: &mut [u8], table: &[u8; 16]) -> usize {
+    let lut = vld1q_u8(table.as_ptr());
+    let mask = vdupq_n_u8(0x0f);
+    let mut i = 0;
+    while i + 16 <= input.len() {
+        let bytes = vld1q_u8(input.as_ptr().add(i));
+        let high = vqtbl1q_u8(lut, vshrq_n_u8::<4>(bytes));
+        let low = vqtbl1q_u8(lut, vandq_u8(bytes, mask));
+        // Interleaving store, high and low digits alternate in the output
+        vst2q_u8(out.as_mut_ptr().add(i * 2), uint8x16x2_t(high, low));
+        i += 16;
+    }
+    i
+}
+
+/// Map hex digits to their value, the second vector flags the bytes that were digits
+#[inline(always)]
+unsafe fn hex_values(chars: uint8x16_t) -> (uint8x16_t, uint8x16_t) {
+    let digit = vsubq_u8(chars, vdupq_n_u8(b'0'));
+    let is_digit = vcltq_u8(digit, vdupq_n_u8(10));
+    let alpha = vsubq_u8(vorrq_u8(chars, vdupq_n_u8(0x20)), vdupq_n_u8(b'a'));
+    let is_alpha = vcltq_u8(alpha, vdupq_n_u8(6));
+    let values = vbslq_u8(is_digit, digit, vaddq_u8(alpha, vdupq_n_u8(10)));
+    (values, vorrq_u8(is_digit, is_alpha))
+}
+
+#[target_feature(enable = "neon")]
+pub(super) unsafe fn hex_decode(input: &[u8], out: &mut [u8]) -> usize {
+    let mut i = 0;
+    while i + 32 <= input.len() {
+        // De-interleaving load, high digits land in the first vector
+        let uint8x16x2_t(high, low) = vld2q_u8(input.as_ptr().add(i));
+        let (high, high_valid) = hex_values(high);
+        let (low, low_valid) = hex_values(low);
+        if vminvq_u8(vandq_u8(high_valid, low_valid)) != 0xff {
+            break;
+        }
+        vst1q_u8(out.as_mut_ptr().add(i / 2), vorrq_u8(vshlq_n_u8::<4>(high), low));
+        i += 32;
+    }
+    i
+}
+
+#[target_feature(enable = "neon")]
+pub(super) unsafe fn base64_encode(
+    input: &[u8],
+    out: &mut [u8],
+    alphabet: &Base64Alphabet,
+) -> usize {
+    let symbols = alphabet.symbols.as_ptr();
+    let lut = uint8x16x4_t(
+        vld1q_u8(symbols),
+        vld1q_u8(symbols.add(16)),
+        vld1q_u8(symbols.add(32)),
+        vld1q_u8(symbols.add(48)),
+    );
+    let mask = vdupq_n_u8(0x3f);
+    let mut i = 0;
+    while i + 48 <= input.len() {
+        let uint8x16x3_t(a, b, c) = vld3q_u8(input.as_ptr().add(i));
+        let first = vshrq_n_u8::<2>(a);
+        let second = vandq_u8(vorrq_u8(vshlq_n_u8::<4>(a), vshrq_n_u8::<4>(b)), mask);
+        let third = vandq_u8(vorrq_u8(vshlq_n_u8::<2>(b), vshrq_n_u8::<6>(c)), mask);
+        let fourth = vandq_u8(c, mask);
+        let quads = uint8x16x4_t(
+            vqtbl4q_u8(lut, first),
+            vqtbl4q_u8(lut, second),
+            vqtbl4q_u8(lut, third),
+            vqtbl4q_u8(lut, fourth),
+        );
+        vst4q_u8(out.as_mut_ptr().add(i / 3 * 4), quads);
+        i += 48;
+    }
+    i
+}
+
+/// Map base64 symbols to their 6-bit value, the second vector flags the alphabet symbols
+#[inline(always)]
+unsafe fn base64_values(
+    symbols: uint8x16_t,
+    alphabet: &Base64Alphabet,
+) -> (uint8x16_t, uint8x16_t) {
+    let upper = vsubq_u8(symbols, vdupq_n_u8(b'A'));
+    let is_upper = vcltq_u8(upper, vdupq_n_u8(26));
+    let lower = vsubq_u8(symbols, vdupq_n_u8(b'a'));
+    let is_lower = vcltq_u8(lower, vdupq_n_u8(26));
+    let digit = vsubq_u8(symbols, vdupq_n_u8(b'0'));
+    let is_digit = vcltq_u8(digit, vdupq_n_u8(10));
+    let is_62 = vceqq_u8(symbols, vdupq_n_u8(alphabet.ch62()));
+    let is_63 = vceqq_u8(symbols, vdupq_n_u8(alphabet.ch63()));
+
+    let mut values = vandq_u8(is_63, vdupq_n_u8(63));
+    values = vbslq_u8(is_62, vdupq_n_u8(62), values);
+    values = vbslq_u8(is_digit, vaddq_u8(digit, vdupq_n_u8(52)), values);
+    values = vbslq_u8(is_lower, vaddq_u8(lower, vdupq_n_u8(26)), values);
+    values = vbslq_u8(is_upper, upper, values);
+    let valid = vorrq_u8(vorrq_u8(vorrq_u8(is_upper, is_lower), is_digit), vorrq_u8(is_62, is_63));
+    (values, valid)
+}
+
+#[target_feature(enable = "neon")]
+pub(super) unsafe fn base64_decode(
+    input: &[u8],
+    out: &mut [u8],
+    alphabet: &Base64Alphabet,
+) -> usize {
+    let mut i = 0;
+    while i + 64 <= input.len() {
+        let uint8x16x4_t(a, b, c, d) = vld4q_u8(input.as_ptr().add(i));
+        let (a, a_valid) = base64_values(a, alphabet);
+        let (b, b_valid) = base64_values(b, alphabet);
+        let (c, c_valid) = base64_values(c, alphabet);
+        let (d, d_valid) = base64_values(d, alphabet);
+        let valid = vandq_u8(vandq_u8(a_valid, b_valid), vandq_u8(c_valid, d_valid));
+        if vminvq_u8(valid) != 0xff {
+            break;
+        }
+        let bytes = uint8x16x3_t(
+            vorrq_u8(vshlq_n_u8::<2>(a), vshrq_n_u8::<4>(b)),
+            vorrq_u8(vshlq_n_u8::<4>(b), vshrq_n_u8::<2>(c)),
+            vorrq_u8(vshlq_n_u8::<6>(c), d),
+        );
+        vst3q_u8(out.as_mut_ptr().add(i / 4 * 3), bytes);
+        i += 64;
+    }
+    i
+}
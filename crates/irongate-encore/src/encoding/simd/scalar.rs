@@ -0,0 +1,44 @@
+use super::Base64Alphabet;
+use crate::encoding::hex::nibble;
+
+pub(super) fn hex_encode(input: &[u8], out: &mut [u8], table: &[u8; 16]) {
+    for (byte, pair) in input.iter().zip(out.chunks_exact_mut(2)) {
+        pair[0] = table[usize::from(byte >> 4)];
+        pair[1] = table[usize::from(byte & 0xf)];
+    }
+}
+
+pub(super) fn hex_decode(input: &[u8], out: &mut [u8]) -> usize {
+    for (i, (pair, byte)) in input.chunks_exact(2).zip(out.iter_mut()).enumerate() {
+        match (nibble(pair[0]), nibble(pair[1])) {
+            (Some(high), Some(low)) => *byte = high << 4 | low,
+            _ => return i * 2,
+        }
+    }
+    input.len() / 2 * 2
+}
+
+pub(super) fn base64_encode(input: &[u8], out: &mut [u8], alphabet: &Base64Alphabet) -> usize {
+    for (group, quad) in input.chunks_exact(3).zip(out.chunks_exact_mut(4)) {
+        let bits = u32::from_be_bytes([0, group[0], group[1], group[2]]);
+        for (i, symbol) in quad.iter_mut().enumerate() {
+            *symbol = alphabet.symbols[(bits >> (18 - 6 * i) & 0x3f) as usize];
+        }
+    }
+    input.len() / 3 * 3
+}
+
+pub(super) fn base64_decode(input: &[u8], out: &mut [u8], alphabet: &Base64Alphabet) -> usize {
+    for (i, (quad, group)) in input.chunks_exact(4).zip(out.chunks_exact_mut(3)).enumerate() {
+        let mut bits = 0;
+        for symbol in quad {
+            let value = alphabet.values[usize::from(*symbol)];
+            if value == Base64Alphabet::INVALID {
+                return i * 4;
+            }
+            bits = bits << 6 | u32::from(value);
+        }
+        group.copy_from_slice(&bits.to_be_bytes()[1..]);
+    }
+    input.len() / 4 * 4
+}
@@ -0,0 +1,146 @@
+use core::arch::wasm32::*;
+
+use super::Base64Alphabet;
+
+#[target_feature(enable = "simd128")]
+pub(super) unsafe fn hex_encode(input: &[u8], out: &mut [u8], table: &[u8; 16]) -> usize {
+    let lut = v128_load(table.as_ptr().cast());
+    let mask = u8x16_splat(0x0f);
+    let mut i = 0;
+    while i + 16 <= input.len() {
+        let bytes = v128_load(input.as_ptr().add(i).cast());
+        let high = u8x16_swizzle(lut, u8x16_shr(bytes, 4));
+        let low = u8x16_swizzle(lut, v128_and(bytes, mask));
+        let dst = out.as_mut_ptr().add(i * 2);
+        v128_store(
+            dst.cast(),
+            u8x16_shuffle::<0, 16, 1, 17, 2, 18, 3, 19, 4, 20, 5, 21, 6, 22, 7, 23>(high, low),
+        );
+        v128_store(
+            dst.add(16).cast(),
+            u8x16_shuffle::<8, 24, 9, 25, 10, 26, 11, 27, 12, 28, 13, 29, 14, 30, 15, 31>(
+                high, low,
+            ),
+        );
+        i += 16;
+    }
+    i
+}
+
+/// Map hex digits to their value, the second vector flags the bytes that were digits
+#[inline(always)]
+unsafe fn hex_values(chars: v128) -> (v128, v128) {
+    let digit = u8x16_sub(chars, u8x16_splat(b'0'));
+    let is_digit = u8x16_lt(digit, u8x16_splat(10));
+    let alpha = u8x16_sub(v128_or(chars, u8x16_splat(0x20)), u8x16_splat(b'a'));
+    let is_alpha = u8x16_lt(alpha, u8x16_splat(6));
+    let values = v128_bitselect(digit, u8x16_add(alpha, u8x16_splat(10)), is_digit);
+    (values, v128_or(is_digit, is_alpha))
+}
+
+#[target_feature(enable = "simd128")]
+pub(super) unsafe fn hex_decode(input: &[u8], out: &mut [u8]) -> usize {
+    let mut i = 0;
+    while i + 32 <= input.len() {
+        let first = v128_load(input.as_ptr().add(i).cast());
+        let second = v128_load(input.as_ptr().add(i + 16).cast());
+        let high = u8x16_shuffle::<0, 2, 4, 6, 8, 10, 12, 14, 16, 18, 20, 22, 24, 26, 28, 30>(
+            first, second,
+        );
+        let low = u8x16_shuffle::<1, 3, 5, 7, 9, 11, 13, 15, 17, 19, 21, 23, 25, 27, 29, 31>(
+            first, second,
+        );
+        let (high, high_valid) = hex_values(high);
+        let (low, low_valid) = hex_values(low);
+        if !u8x16_all_true(v128_and(high_valid, low_valid)) {
+            break;
+        }
+        v128_store(out.as_mut_ptr().add(i / 2).cast(), v128_or(u8x16_shl(high, 4), low));
+        i += 32;
+    }
+    i
+}
+
+#[target_feature(enable = "simd128")]
+pub(super) unsafe fn base64_encode(
+    input: &[u8],
+    out: &mut [u8],
+    alphabet: &Base64Alphabet,
+) -> usize {
+    let shift_lut = alphabet.shift_lut();
+    let shift_lut = v128_load(shift_lut.as_ptr().cast());
+    let low_halves = u32x4_splat(0xffff);
+    let mut i = 0;
+    let mut o = 0;
+    // Blocks of 12 bytes are read with 16 byte loads
+    while i + 16 <= input.len() {
+        let bytes = v128_load(input.as_ptr().add(i).cast());
+        let bytes = u8x16_swizzle(bytes, u8x16(1, 0, 2, 1, 4, 3, 5, 4, 7, 6, 8, 7, 10, 9, 11, 10));
+        // Per-lane shifts stand in for the multiplications of the x86 kernel
+        let high = v128_and(bytes, u32x4_splat(0x0fc0fc00));
+        let high = v128_bitselect(u16x8_shr(high, 10), u16x8_shr(high, 6), low_halves);
+        let low = v128_and(bytes, u32x4_splat(0x003f03f0));
+        let low = v128_bitselect(u16x8_shl(low, 4), u16x8_shl(low, 8), low_halves);
+        let indices = v128_or(high, low);
+
+        let ranges = u8x16_sub_sat(indices, u8x16_splat(51));
+        let uppercase = i8x16_gt(i8x16_splat(26), indices);
+        let ranges = v128_or(ranges, v128_and(uppercase, u8x16_splat(13)));
+        let symbols = u8x16_add(u8x16_swizzle(shift_lut, ranges), indices);
+
+        v128_store(out.as_mut_ptr().add(o).cast(), symbols);
+        i += 12;
+        o += 16;
+    }
+    i
+}
+
+/// Map base64 symbols to their 6-bit value, the second vector flags the alphabet symbols
+#[inline(always)]
+unsafe fn base64_values(symbols: v128, alphabet: &Base64Alphabet) -> (v128, v128) {
+    let upper = u8x16_sub(symbols, u8x16_splat(b'A'));
+    let is_upper = u8x16_lt(upper, u8x16_splat(26));
+    let lower = u8x16_sub(symbols, u8x16_splat(b'a'));
+    let is_lower = u8x16_lt(lower, u8x16_splat(26));
+    let digit = u8x16_sub(symbols, u8x16_splat(b'0'));
+    let is_digit = u8x16_lt(digit, u8x16_splat(10));
+    let is_62 = u8x16_eq(symbols, u8x16_splat(alphabet.ch62()));
+    let is_63 = u8x16_eq(symbols, u8x16_splat(alphabet.ch63()));
+
+    let mut values = v128_and(is_63, u8x16_splat(63));
+    values = v128_bitselect(u8x16_splat(62), values, is_62);
+    values = v128_bitselect(u8x16_add(digit, u8x16_splat(52)), values, is_digit);
+    values = v128_bitselect(u8x16_add(lower, u8x16_splat(26)), values, is_lower);
+    values = v128_bitselect(upper, values, is_upper);
+    let valid = v128_or(v128_or(v128_or(is_upper, is_lower), is_digit), v128_or(is_62, is_63));
+    (values, valid)
+}
+
+#[target_feature(enable = "simd128")]
+pub(super) unsafe fn base64_decode(
+    input: &[u8],
+    out: &mut [u8],
+    alphabet: &Base64Alphabet,
+) -> usize {
+    let mut i = 0;
+    let mut o = 0;
+    // Blocks of 12 bytes are written with 16 byte stores
+    while i + 16 <= input.len() && o + 16 <= out.len() {
+        let symbols = v128_load(input.as_ptr().add(i).cast());
+        let (values, valid) = base64_values(symbols, alphabet);
+        if !u8x16_all_true(valid) {
+            break;
+        }
+        // Merge pairs of 6-bit values into 12 bits, then pairs of those into 24 bits
+        let pairs =
+            v128_or(u16x8_shl(v128_and(values, u16x8_splat(0xff)), 6), u16x8_shr(values, 8));
+        let triples =
+            v128_or(u32x4_shl(v128_and(pairs, u32x4_splat(0xffff)), 12), u32x4_shr(pairs, 16));
+        let bytes =
+            u8x16_swizzle(triples, u8x16(2, 1, 0, 6, 5, 4, 10, 9, 8, 14, 13, 12, 16, 16, 16, 16));
+        v128_store(out.as_mut_ptr().add(o).cast(), bytes);
+        i += 16;
+        o += 12;
+    }
+    i
+}
@@ -0,0 +1,325 @@
+#[cfg(target_arch = "x86")]
+use core::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::*;
+
+use super::Base64Alphabet;
+
+#[target_feature(enable = "ssse3")]
+pub(super) unsafe fn hex_encode_ssse3(input: &[u8], out: &mut [u8], table: &[u8; 16]) -> usize {
+    let lut = _mm_loadu_si128(table.as_ptr().cast());
+    let mask = _mm_set1_epi8(0x0f);
+    let mut i = 0;
+    while i + 16 <= input.len() {
+        let bytes = _mm_loadu_si128(input.as_ptr().add(i).cast());
+        let high = _mm_shuffle_epi8(lut, _mm_and_si128(_mm_srli_epi16(bytes, 4), mask));
+        let low = _mm_shuffle_epi8(lut, _mm_and_si128(bytes, mask));
+        let dst = out.as_mut_ptr().add(i * 2);
+        _mm_storeu_si128(dst.cast(), _mm_unpacklo_epi8(high, low));
+        _mm_storeu_si128(dst.add(16).cast(), _mm_unpackhi_epi8(high, low));
+        i += 16;
+    }
+    i
+}
+
+#[target_feature(enable = "avx2")]
+pub(super) unsafe fn hex_encode_avx2(input: &[u8], out: &mut [u8], table: &[u8; 16]) -> usize {
+    let lut = _mm256_broadcastsi128_si256(_mm_loadu_si128(table.as_ptr().cast()));
+    let mask = _mm256_set1_epi8(0x0f);
+    let mut i = 0;
+    while i + 32 <= input.len() {
+        let bytes = _mm256_loadu_si256(input.as_ptr().add(i).cast());
+        let high = _mm256_shuffle_epi8(lut, _mm256_and_si256(_mm256_srli_epi16(bytes, 4), mask));
+        let low = _mm256_shuffle_epi8(lut, _mm256_and_si256(bytes, mask));
+        // Unpacking works within 128-bit lanes, swap the middle halves back in order
+        let first = _mm256_unpacklo_epi8(high, low);
+        let second = _mm256_unpackhi_epi8(high, low);
+        let dst = out.as_mut_ptr().add(i * 2);
+        _mm256_storeu_si256(dst.cast(), _mm256_permute2x128_si256(first, second, 0x20));
+        _mm256_storeu_si256(dst.add(32).cast(), _mm256_permute2x128_si256(first, second, 0x31));
+        i += 32;
+    }
+    i + hex_encode_ssse3(&input[i..], &mut out[i * 2..], table)
+}
+
+/// Map hex digits to their value, the second vector flags the bytes that were digits
+#[inline(always)]
+unsafe fn hex_values_ssse3(chars: __m128i) -> (__m128i, __m128i) {
+    let digit = _mm_sub_epi8(chars, _mm_set1_epi8(b'0' as i8));
+    let is_digit = _mm_cmpeq_epi8(_mm_min_epu8(digit, _mm_set1_epi8(9)), digit);
+    let alpha = _mm_sub_epi8(_mm_or_si128(chars, _mm_set1_epi8(0x20)), _mm_set1_epi8(b'a' as i8));
+    let is_alpha = _mm_cmpeq_epi8(_mm_min_epu8(alpha, _mm_set1_epi8(5)), alpha);
+    let values = _mm_or_si128(
+        _mm_and_si128(is_digit, digit),
+        _mm_andnot_si128(is_digit, _mm_add_epi8(alpha, _mm_set1_epi8(10))),
+    );
+    (values, _mm_or_si128(is_digit, is_alpha))
+}
+
+#[target_feature(enable = "ssse3")]
+pub(super) unsafe fn hex_decode_ssse3(input: &[u8], out: &mut [u8]) -> usize {
+    // Pairs of nibbles are merged as `high * 16 + low`
+    let weights = _mm_set1_epi16(0x0110);
+    let mut i = 0;
+    while i + 32 <= input.len() {
+        let (first, first_valid) = hex_values_ssse3(_mm_loadu_si128(input.as_ptr().add(i).cast()));
+        let (second, second_valid) =
+            hex_values_ssse3(_mm_loadu_si128(input.as_ptr().add(i + 16).cast()));
+        if _mm_movemask_epi8(_mm_and_si128(first_valid, second_valid)) != 0xffff {
+            break;
+        }
+        let bytes =
+            _mm_packus_epi16(_mm_maddubs_epi16(first, weights), _mm_maddubs_epi16(second, weights));
+        _mm_storeu_si128(out.as_mut_ptr().add(i / 2).cast(), bytes);
+        i += 32;
+    }
+    i
+}
+
+#[inline(always)]
+unsafe fn hex_values_avx2(chars: __m256i) -> (__m256i, __m256i) {
+    let digit = _mm256_sub_epi8(chars, _mm256_set1_epi8(b'0' as i8));
+    let is_digit = _mm256_cmpeq_epi8(_mm256_min_epu8(digit, _mm256_set1_epi8(9)), digit);
+    let alpha = _mm256_sub_epi8(
+        _mm256_or_si256(chars, _mm256_set1_epi8(0x20)),
+        _mm256_set1_epi8(b'a' as i8),
+    );
+    let is_alpha = _mm256_cmpeq_epi8(_mm256_min_epu8(alpha, _mm256_set1_epi8(5)), alpha);
+    let values = _mm256_or_si256(
+        _mm256_and_si256(is_digit, digit),
+        _mm256_andnot_si256(is_digit, _mm256_add_epi8(alpha, _mm256_set1_epi8(10))),
+    );
+    (values, _mm256_or_si256(is_digit, is_alpha))
+}
+
+#[target_feature(enable = "avx2")]
+pub(super) unsafe fn hex_decode_avx2(input: &[u8], out: &mut [u8]) -> usize {
+    let weights = _mm256_set1_epi16(0x0110);
+    let mut i = 0;
+    while i + 64 <= input.len() {
+        let (first, first_valid) =
+            hex_values_avx2(_mm256_loadu_si256(input.as_ptr().add(i).cast()));
+        let (second, second_valid) =
+            hex_values_avx2(_mm256_loadu_si256(input.as_ptr().add(i + 32).cast()));
+        if _mm256_movemask_epi8(_mm256_and_si256(first_valid, second_valid)) != -1 {
+            break;
+        }
+        let packed = _mm256_packus_epi16(
+            _mm256_maddubs_epi16(first, weights),
+            _mm256_maddubs_epi16(second, weights),
+        );
+        // Packing interleaves the 64-bit quarters of both lanes
+        let bytes = _mm256_permute4x64_epi64(packed, 0b11_01_10_00);
+        _mm256_storeu_si256(out.as_mut_ptr().add(i / 2).cast(), bytes);
+        i += 64;
+    }
+    i + hex_decode_ssse3(&input[i..], &mut out[i / 2..])
+}
+
+/// Spread three bytes over four 6-bit indices per 32-bit lane
+#[inline(always)]
+unsafe fn base64_indices_ssse3(bytes: __m128i) -> __m128i {
+    let bytes =
+        _mm_shuffle_epi8(bytes, _mm_setr_epi8(1, 0, 2, 1, 4, 3, 5, 4, 7, 6, 8, 7, 10, 9, 11, 10));
+    let high = _mm_mulhi_epu16(
+        _mm_and_si128(bytes, _mm_set1_epi32(0x0fc0fc00)),
+        _mm_set1_epi32(0x04000040),
+    );
+    let low = _mm_mullo_epi16(
+        _mm_and_si128(bytes, _mm_set1_epi32(0x003f03f0)),
+        _mm_set1_epi32(0x01000010),
+    );
+    _mm_or_si128(high, low)
+}
+
+#[inline(always)]
+unsafe fn base64_symbols_ssse3(indices: __m128i, shift_lut: __m128i) -> __m128i {
+    let mut ranges = _mm_subs_epu8(indices, _mm_set1_epi8(51));
+    let uppercase = _mm_cmpgt_epi8(_mm_set1_epi8(26), indices);
+    ranges = _mm_or_si128(ranges, _mm_and_si128(uppercase, _mm_set1_epi8(13)));
+    _mm_add_epi8(_mm_shuffle_epi8(shift_lut, ranges), indices)
+}
+
+#[target_feature(enable = "ssse3")]
+pub(super) unsafe fn base64_encode_ssse3(
+    input: &[u8],
+    out: &mut [u8],
+    alphabet: &Base64Alphabet,
+) -> usize {
+    let shift_lut = _mm_loadu_si128(alphabet.shift_lut().as_ptr().cast());
+    let mut i = 0;
+    let mut o = 0;
+    // Blocks of 12 bytes are read with 16 byte loads
+    while i + 16 <= input.len() {
+        let indices = base64_indices_ssse3(_mm_loadu_si128(input.as_ptr().add(i).cast()));
+        let symbols = base64_symbols_ssse3(indices, shift_lut);
+        _mm_storeu_si128(out.as_mut_ptr().add(o).cast(), symbols);
+        i += 12;
+        o += 16;
+    }
+    i
+}
+
+#[target_feature(enable = "avx2")]
+pub(super) unsafe fn base64_encode_avx2(
+    input: &[u8],
+    out: &mut [u8],
+    alphabet: &Base64Alphabet,
+) -> usize {
+    let shift_lut =
+        _mm256_broadcastsi128_si256(_mm_loadu_si128(alphabet.shift_lut().as_ptr().cast()));
+    let reshuffle = _mm256_setr_epi8(
+        1, 0, 2, 1, 4, 3, 5, 4, 7, 6, 8, 7, 10, 9, 11, 10, 1, 0, 2, 1, 4, 3, 5, 4, 7, 6, 8, 7, 10,
+        9, 11, 10,
+    );
+    let mut i = 0;
+    let mut o = 0;
+    // Each lane gets its own block of 12 bytes
+    while i + 28 <= input.len() {
+        let first = _mm_loadu_si128(input.as_ptr().add(i).cast());
+        let second = _mm_loadu_si128(input.as_ptr().add(i + 12).cast());
+        let bytes = _mm256_shuffle_epi8(
+            _mm256_inserti128_si256(_mm256_castsi128_si256(first), second, 1),
+            reshuffle,
+        );
+        let high = _mm256_mulhi_epu16(
+            _mm256_and_si256(bytes, _mm256_set1_epi32(0x0fc0fc00)),
+            _mm256_set1_epi32(0x04000040),
+        );
+        let low = _mm256_mullo_epi16(
+            _mm256_and_si256(bytes, _mm256_set1_epi32(0x003f03f0)),
+            _mm256_set1_epi32(0x01000010),
+        );
+        let indices = _mm256_or_si256(high, low);
+
+        let mut ranges = _mm256_subs_epu8(indices, _mm256_set1_epi8(51));
+        let uppercase = _mm256_cmpgt_epi8(_mm256_set1_epi8(26), indices);
+        ranges = _mm256_or_si256(ranges, _mm256_and_si256(uppercase, _mm256_set1_epi8(13)));
+        let symbols = _mm256_add_epi8(_mm256_shuffle_epi8(shift_lut, ranges), indices);
+
+        _mm256_storeu_si256(out.as_mut_ptr().add(o).cast(), symbols);
+        i += 24;
+        o += 32;
+    }
+    i + base64_encode_ssse3(&input[i..], &mut out[o..], alphabet)
+}
+
+/// Map base64 symbols to their 6-bit value, the second vector flags the alphabet symbols
+#[inline(always)]
+unsafe fn base64_values_ssse3(symbols: __m128i, alphabet: &Base64Alphabet) -> (__m128i, __m128i) {
+    let upper = _mm_sub_epi8(symbols, _mm_set1_epi8(b'A' as i8));
+    let is_upper = _mm_cmpeq_epi8(_mm_min_epu8(upper, _mm_set1_epi8(25)), upper);
+    let lower = _mm_sub_epi8(symbols, _mm_set1_epi8(b'a' as i8));
+    let is_lower = _mm_cmpeq_epi8(_mm_min_epu8(lower, _mm_set1_epi8(25)), lower);
+    let digit = _mm_sub_epi8(symbols, _mm_set1_epi8(b'0' as i8));
+    let is_digit = _mm_cmpeq_epi8(_mm_min_epu8(digit, _mm_set1_epi8(9)), digit);
+    let is_62 = _mm_cmpeq_epi8(symbols, _mm_set1_epi8(alphabet.ch62() as i8));
+    let is_63 = _mm_cmpeq_epi8(symbols, _mm_set1_epi8(alphabet.ch63() as i8));
+
+    let values = _mm_or_si128(
+        _mm_or_si128(
+            _mm_and_si128(is_upper, upper),
+            _mm_and_si128(is_lower, _mm_add_epi8(lower, _mm_set1_epi8(26))),
+        ),
+        _mm_or_si128(
+            _mm_and_si128(is_digit, _mm_add_epi8(digit, _mm_set1_epi8(52))),
+            _mm_or_si128(
+                _mm_and_si128(is_62, _mm_set1_epi8(62)),
+                _mm_and_si128(is_63, _mm_set1_epi8(63)),
+            ),
+        ),
+    );
+    let valid = _mm_or_si128(
+        _mm_or_si128(_mm_or_si128(is_upper, is_lower), is_digit),
+        _mm_or_si128(is_62, is_63),
+    );
+    (values, valid)
+}
+
+/// Merge four 6-bit values per 32-bit lane into three bytes, stored in the first 12 bytes
+#[inline(always)]
+unsafe fn base64_pack_ssse3(values: __m128i) -> __m128i {
+    let pairs = _mm_maddubs_epi16(values, _mm_set1_epi32(0x01400140));
+    let triples = _mm_madd_epi16(pairs, _mm_set1_epi32(0x00011000));
+    _mm_shuffle_epi8(triples, _mm_setr_epi8(2, 1, 0, 6, 5, 4, 10, 9, 8, 14, 13, 12, -1, -1, -1, -1))
+}
+
+#[target_feature(enable = "ssse3")]
+pub(super) unsafe fn base64_decode_ssse3(
+    input: &[u8],
+    out: &mut [u8],
+    alphabet: &Base64Alphabet,
+) -> usize {
+    let mut i = 0;
+    let mut o = 0;
+    // Blocks of 12 bytes are written with 16 byte stores
+    while i + 16 <= input.len() && o + 16 <= out.len() {
+        let symbols = _mm_loadu_si128(input.as_ptr().add(i).cast());
+        let (values, valid) = base64_values_ssse3(symbols, alphabet);
+        if _mm_movemask_epi8(valid) != 0xffff {
+            break;
+        }
+        _mm_storeu_si128(out.as_mut_ptr().add(o).cast(), base64_pack_ssse3(values));
+        i += 16;
+        o += 12;
+    }
+    i
+}
+
+#[target_feature(enable = "avx2")]
+pub(super) unsafe fn base64_decode_avx2(
+    input: &[u8],
+    out: &mut [u8],
+    alphabet: &Base64Alphabet,
+) -> usize {
+    let mut i = 0;
+    let mut o = 0;
+    while i + 32 <= input.len() && o + 32 <= out.len() {
+        let symbols = _mm256_loadu_si256(input.as_ptr().add(i).cast());
+        let upper = _mm256_sub_epi8(symbols, _mm256_set1_epi8(b'A' as i8));
+        let is_upper = _mm256_cmpeq_epi8(_mm256_min_epu8(upper, _mm256_set1_epi8(25)), upper);
+        let lower = _mm256_sub_epi8(symbols, _mm256_set1_epi8(b'a' as i8));
+        let is_lower = _mm256_cmpeq_epi8(_mm256_min_epu8(lower, _mm256_set1_epi8(25)), lower);
+        let digit = _mm256_sub_epi8(symbols, _mm256_set1_epi8(b'0' as i8));
+        let is_digit = _mm256_cmpeq_epi8(_mm256_min_epu8(digit, _mm256_set1_epi8(9)), digit);
+        let is_62 = _mm256_cmpeq_epi8(symbols, _mm256_set1_epi8(alphabet.ch62() as i8));
+        let is_63 = _mm256_cmpeq_epi8(symbols, _mm256_set1_epi8(alphabet.ch63() as i8));
+
+        let valid = _mm256_or_si256(
+            _mm256_or_si256(_mm256_or_si256(is_upper, is_lower), is_digit),
+            _mm256_or_si256(is_62, is_63),
+        );
+        if _mm256_movemask_epi8(valid) != -1 {
+            break;
+        }
+        let values = _mm256_or_si256(
+            _mm256_or_si256(
+                _mm256_and_si256(is_upper, upper),
+                _mm256_and_si256(is_lower, _mm256_add_epi8(lower, _mm256_set1_epi8(26))),
+            ),
+            _mm256_or_si256(
+                _mm256_and_si256(is_digit, _mm256_add_epi8(digit, _mm256_set1_epi8(52))),
+                _mm256_or_si256(
+                    _mm256_and_si256(is_62, _mm256_set1_epi8(62)),
+                    _mm256_and_si256(is_63, _mm256_set1_epi8(63)),
+                ),
+            ),
+        );
+
+        let pairs = _mm256_maddubs_epi16(values, _mm256_set1_epi32(0x01400140));
+        let triples = _mm256_madd_epi16(pairs, _mm256_set1_epi32(0x00011000));
+        let packed = _mm256_shuffle_epi8(
+            triples,
+            _mm256_setr_epi8(
+                2, 1, 0, 6, 5, 4, 10, 9, 8, 14, 13, 12, -1, -1, -1, -1, 2, 1, 0, 6, 5, 4, 10, 9, 8,
+                14, 13, 12, -1, -1, -1, -1,
+            ),
+        );
+        // Move the 12 bytes of the upper lane next to the ones of the lower lane
+        let bytes = _mm256_permutevar8x32_epi32(packed, _mm256_setr_epi32(0, 1, 2, 4, 5, 6, 3, 7));
+        _mm256_storeu_si256(out.as_mut_ptr().add(o).cast(), bytes);
+        i += 32;
+        o += 24;
+    }
+    i + base64_decode_ssse3(&input[i..], &mut out[o..], alphabet)
+}
@@ -1,10 +1,18 @@
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
 use bstr::ByteSlice;
 use percent_encoding::{self, percent_encode_byte};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "registry")]
+use serde_json::{json, Value};
 
 use crate::Operation;
 use crate::OperationError;
+use crate::Reversible;
+#[cfg(feature = "registry")]
+use crate::{Category, OperationInfo};
 
 #[derive(Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -12,8 +20,14 @@ pub struct UrlDecode {}
 
 impl Operation for UrlDecode {
     fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
-        let decoded: Vec<u8> = percent_encoding::percent_decode(input).collect();
-        Ok(decoded)
+        let mut output = vec![];
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        out.extend(percent_encoding::percent_decode(input));
+        Ok(())
     }
 }
 
@@ -26,7 +40,9 @@ impl UrlDecode {
 #[derive(Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct UrlEncode {
+    #[cfg_attr(feature = "serde", serde(default))]
     non_ascii: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
     charset: String,
 }
 
@@ -36,21 +52,27 @@ fn extend_with_grapheme_encode(output: &mut Vec<u8>, grapheme: &[u8]) {
 
 impl Operation for UrlEncode {
     fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
+        let mut output = vec![];
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
         let charset = self.charset.as_bytes().graphemes();
         let input_graphemes = input.grapheme_indices();
-        let mut output: Vec<u8> = vec![];
+        out.reserve(input.len());
 
         for (start, stop, grapheme) in input_graphemes {
             let mut charset_graphemes = charset.clone();
             if charset_graphemes.any(|charset_grapheme| charset_grapheme == grapheme)
                 || (self.non_ascii && !grapheme.is_ascii())
             {
-                extend_with_grapheme_encode(&mut output, &input[start..stop]);
+                extend_with_grapheme_encode(out, &input[start..stop]);
             } else {
-                output.extend_from_slice(&input[start..stop]);
+                out.extend_from_slice(&input[start..stop]);
             }
         }
-        Ok(output)
+        Ok(())
     }
 }
 
@@ -60,8 +82,63 @@ impl UrlEncode {
     }
 }
 
+/// The inverse percent-encodes `%` and every non-ASCII grapheme, which makes
+/// any input survive the round trip
+impl Reversible for UrlDecode {
+    type Inverse = UrlEncode;
+
+    fn inverse(&self) -> UrlEncode {
+        UrlEncode::new(true, Some("%".to_string()))
+    }
+}
+
+/// Round trips only hold when `%` is part of the charset, otherwise existing
+/// percent sequences in the input are decoded as well
+impl Reversible for UrlEncode {
+    type Inverse = UrlDecode;
+
+    fn inverse(&self) -> UrlDecode {
+        UrlDecode::new()
+    }
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for UrlDecode {
+    const ID: &'static str = "url_decode";
+    const NAME: &'static str = "URL Decode";
+    const CATEGORY: Category = Category::Encoding;
+    const DESCRIPTION: &'static str = "Decode percent-encoded bytes";
+
+    fn options_schema() -> Value {
+        json!({ "type": "object", "properties": {} })
+    }
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for UrlEncode {
+    const ID: &'static str = "url_encode";
+    const NAME: &'static str = "URL Encode";
+    const CATEGORY: Category = Category::Encoding;
+    const DESCRIPTION: &'static str =
+        "Percent-encode the characters of a charset and optionally all non-ASCII";
+    // Spelling of the WASM bindings
+    const OPTION_ALIASES: &'static [(&'static str, &'static str)] = &[("nonAscii", "non_ascii")];
+
+    fn options_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "non_ascii": { "type": "boolean", "default": false },
+                "charset": { "type": "string", "default": "" }
+            }
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     #[test]
@@ -121,4 +198,27 @@ mod tests {
         let expected = "%98%FD%E0%BF%B8%A7%D6%E1t%A0".as_bytes().to_vec();
         assert_eq!(actual, expected);
     }
+
+    proptest! {
+        #[test]
+        fn url_round_trip(input: Vec<u8>, non_ascii: bool, charset in "[a-z@ /?=&]*") {
+            let encoder = UrlEncode::new(non_ascii, Some(format!("%{charset}")));
+            let encoded = encoder.execute(&input).unwrap();
+            prop_assert_eq!(encoder.inverse().execute(&encoded).unwrap(), input);
+        }
+
+        #[test]
+        fn url_decode_inverse_round_trip(input: Vec<u8>) {
+            let decoder = UrlDecode::new();
+            let encoded = decoder.inverse().execute(&input).unwrap();
+            prop_assert_eq!(decoder.execute(&encoded).unwrap(), input);
+        }
+
+        #[test]
+        fn url_round_trip_percent_sequences(input in "(%[0-9A-Fa-f]{0,2}|[a-z]|é)*") {
+            let decoder = UrlDecode::new();
+            let encoded = decoder.inverse().execute(input.as_bytes()).unwrap();
+            prop_assert_eq!(decoder.execute(&encoded).unwrap(), input.as_bytes());
+        }
+    }
 }
@@ -1,28 +1,50 @@
+#[cfg(feature = "encode")]
+use alloc::string::ToString;
+use alloc::string::{FromUtf8Error, String};
+use core::fmt;
 use core::str::Utf8Error;
-use std::string::FromUtf8Error;
-use thiserror::Error;
 
-#[derive(Debug, Error)]
+#[derive(Debug)]
 pub enum OperationError {
-    #[error("Utf8 decode error")]
     DecodeUtf8Error,
-
-    #[error("Decode error: {0}")]
-    DecodeError(String),
-
-    #[error("Encryption error: {0}")]
+    /// Malformed input, `offset` is the position of the first invalid byte when the decoder knows it
+    DecodeError {
+        message: String,
+        offset: Option<usize>,
+    },
     EncryptionError(String),
-
-    #[error("IO error: {0}")]
     IoError(String),
-
-    #[error("Not implemented: {0}")]
+    InvalidOptions(String),
+    UnknownOperation(String),
     NotImplemented(String),
-
-    #[error("Unknown error")]
     Unknown,
 }
 
+impl fmt::Display for OperationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OperationError::DecodeUtf8Error => write!(f, "Utf8 decode error"),
+            OperationError::DecodeError { message, .. } => write!(f, "Decode error: {message}"),
+            OperationError::EncryptionError(message) => write!(f, "Encryption error: {message}"),
+            OperationError::IoError(message) => write!(f, "IO error: {message}"),
+            OperationError::InvalidOptions(message) => write!(f, "Invalid options: {message}"),
+            OperationError::UnknownOperation(id) => write!(f, "Unknown operation: {id}"),
+            OperationError::NotImplemented(message) => write!(f, "Not implemented: {message}"),
+            OperationError::Unknown => write!(f, "Unknown error"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OperationError {}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for OperationError {
+    fn from(value: std::io::Error) -> Self {
+        OperationError::IoError(value.to_string())
+    }
+}
+
 impl From<Utf8Error> for OperationError {
     fn from(_value: Utf8Error) -> Self {
         OperationError::DecodeUtf8Error
@@ -37,14 +59,18 @@ impl From<FromUtf8Error> for OperationError {
 
 #[cfg(feature = "encode")]
 impl From<hex::FromHexError> for OperationError {
-    fn from(_value: hex::FromHexError) -> Self {
-        OperationError::DecodeError("Invalid Hex input".to_string())
+    fn from(value: hex::FromHexError) -> Self {
+        let offset = match value {
+            hex::FromHexError::InvalidHexCharacter { index, .. } => Some(index),
+            _ => None,
+        };
+        OperationError::DecodeError { message: "Invalid Hex input".to_string(), offset }
     }
 }
 
 #[cfg(feature = "encode")]
 impl From<data_encoding::DecodeError> for OperationError {
     fn from(value: data_encoding::DecodeError) -> Self {
-        OperationError::DecodeError(value.to_string())
+        OperationError::DecodeError { message: value.to_string(), offset: Some(value.position) }
     }
 }
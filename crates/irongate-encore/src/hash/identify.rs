@@ -0,0 +1,365 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use base64::Engine;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::HashAlgorithm;
+use crate::Operation;
+use crate::OperationError;
+#[cfg(feature = "registry")]
+use crate::{Category, OperationInfo};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Confidence {
+    High,
+    Medium,
+    Low,
+}
+
+impl Confidence {
+    const fn name(self) -> &'static str {
+        match self {
+            Confidence::High => "high",
+            Confidence::Medium => "medium",
+            Confidence::Low => "low",
+        }
+    }
+}
+
+/// How a candidate is checked against a plaintext
+#[derive(Clone, Copy)]
+enum Check {
+    /// Hex digest of the plaintext, in either case
+    Hex(HashAlgorithm),
+    /// `*` and the hex SHA-1 of the SHA-1 of the plaintext, as stored by MySQL 4.1+
+    Mysql41,
+    /// RFC 2307 base64 digest, followed by the salt that was appended to the plaintext
+    Ldap { algorithm: HashAlgorithm, salted: bool },
+    /// The algorithm is not implemented by this crate
+    Unsupported,
+}
+
+struct Candidate {
+    name: &'static str,
+    confidence: Confidence,
+    check: Check,
+}
+
+const fn candidate(name: &'static str, confidence: Confidence, check: Check) -> Candidate {
+    Candidate { name, confidence, check }
+}
+
+/// Modular crypt formats and the like, identified by their prefix alone
+const PREFIXES: &[(&str, &str)] = &[
+    ("$2a$", "bcrypt"),
+    ("$2b$", "bcrypt"),
+    ("$2x$", "bcrypt"),
+    ("$2y$", "bcrypt"),
+    ("$argon2id$", "argon2id"),
+    ("$argon2i$", "argon2i"),
+    ("$argon2d$", "argon2d"),
+    ("$scrypt$", "scrypt"),
+    ("$7$", "scrypt"),
+    ("$y$", "yescrypt"),
+    ("$1$", "md5crypt"),
+    ("$apr1$", "apr1"),
+    ("$5$", "sha256crypt"),
+    ("$6$", "sha512crypt"),
+    ("$sha1$", "sha1crypt"),
+    ("$P$", "phpass"),
+    ("$H$", "phpass"),
+    ("$pbkdf2-sha1$", "pbkdf2_sha1"),
+    ("$pbkdf2-sha256$", "pbkdf2_sha256"),
+    ("$pbkdf2-sha512$", "pbkdf2_sha512"),
+];
+
+/// RFC 2307 `{SCHEME}` prefixes of LDAP password hashes
+const LDAP_SCHEMES: &[(&str, &str, Check)] = &[
+    ("{SSHA}", "ldap_ssha", Check::Ldap { algorithm: HashAlgorithm::Sha1, salted: true }),
+    ("{SHA}", "ldap_sha", Check::Ldap { algorithm: HashAlgorithm::Sha1, salted: false }),
+    ("{SSHA256}", "ldap_ssha256", Check::Ldap { algorithm: HashAlgorithm::Sha256, salted: true }),
+    ("{SHA256}", "ldap_sha256", Check::Ldap { algorithm: HashAlgorithm::Sha256, salted: false }),
+    ("{SSHA512}", "ldap_ssha512", Check::Ldap { algorithm: HashAlgorithm::Sha512, salted: true }),
+    ("{SHA512}", "ldap_sha512", Check::Ldap { algorithm: HashAlgorithm::Sha512, salted: false }),
+    ("{SMD5}", "ldap_smd5", Check::Ldap { algorithm: HashAlgorithm::Md5, salted: true }),
+    ("{MD5}", "ldap_md5", Check::Ldap { algorithm: HashAlgorithm::Md5, salted: false }),
+    ("{CRYPT}", "ldap_crypt", Check::Unsupported),
+];
+
+/// Candidates for bare hex digests by length, most common first
+fn hex_candidates(len: usize) -> Vec<Candidate> {
+    use Check::{Hex, Unsupported};
+    use Confidence::{Low, Medium};
+    match len {
+        8 => vec![candidate("crc32", Medium, Unsupported), candidate("adler32", Low, Unsupported)],
+        16 => {
+            vec![candidate("mysql323", Medium, Unsupported), candidate("crc64", Low, Unsupported)]
+        }
+        32 => vec![
+            candidate("md5", Medium, Hex(HashAlgorithm::Md5)),
+            candidate("ntlm", Medium, Unsupported),
+            candidate("md4", Low, Unsupported),
+            candidate("lm", Low, Unsupported),
+        ],
+        40 => vec![
+            candidate("sha1", Medium, Hex(HashAlgorithm::Sha1)),
+            candidate("ripemd160", Low, Unsupported),
+        ],
+        56 => vec![
+            candidate("sha224", Medium, Hex(HashAlgorithm::Sha224)),
+            candidate("sha3_224", Low, Unsupported),
+        ],
+        64 => vec![
+            candidate("sha256", Medium, Hex(HashAlgorithm::Sha256)),
+            candidate("blake3", Low, Hex(HashAlgorithm::Blake3)),
+            candidate("sha3_256", Low, Unsupported),
+            candidate("blake2s", Low, Unsupported),
+        ],
+        96 => vec![
+            candidate("sha384", Medium, Hex(HashAlgorithm::Sha384)),
+            candidate("sha3_384", Low, Unsupported),
+        ],
+        128 => vec![
+            candidate("sha512", Medium, Hex(HashAlgorithm::Sha512)),
+            candidate("sha3_512", Low, Unsupported),
+            candidate("blake2b", Low, Unsupported),
+            candidate("whirlpool", Low, Unsupported),
+        ],
+        _ => vec![],
+    }
+}
+
+fn is_hex(input: &str, len: usize) -> bool {
+    input.len() == len && input.bytes().all(|byte| byte.is_ascii_hexdigit())
+}
+
+/// `LM:NT` pairs and pwdump lines such as `user:500:LM:NT:::`
+fn is_ntlm(input: &str) -> bool {
+    let parts: Vec<&str> = input.split(':').collect();
+    match parts.as_slice() {
+        [lm, nt] => is_hex(lm, 32) && is_hex(nt, 32),
+        [_, rid, lm, nt, ..] => {
+            rid.bytes().all(|byte| byte.is_ascii_digit()) && is_hex(lm, 32) && is_hex(nt, 32)
+        }
+        _ => false,
+    }
+}
+
+fn identify(hash: &str) -> Vec<Candidate> {
+    use Confidence::High;
+    if let Some((_, name)) = PREFIXES.iter().find(|(prefix, _)| hash.starts_with(prefix)) {
+        return vec![candidate(name, High, Check::Unsupported)];
+    }
+    if let Some((_, name, check)) =
+        LDAP_SCHEMES.iter().find(|(scheme, _, _)| starts_with_ignore_case(hash, scheme))
+    {
+        return vec![candidate(name, High, *check)];
+    }
+    if hash.strip_prefix('*').is_some_and(|digest| is_hex(digest, 40)) {
+        return vec![candidate("mysql41", High, Check::Mysql41)];
+    }
+    if is_ntlm(hash) {
+        return vec![candidate("ntlm", High, Check::Unsupported)];
+    }
+    if !hash.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return vec![];
+    }
+    let mut candidates = hex_candidates(hash.len());
+    // Windows tools print NTLM hashes in uppercase, digests are usually lowercase
+    if hash.len() == 32 && !hash.bytes().any(|byte| byte.is_ascii_lowercase()) {
+        candidates.swap(0, 1);
+    }
+    candidates
+}
+
+fn starts_with_ignore_case(hash: &str, prefix: &str) -> bool {
+    hash.get(..prefix.len()).is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+}
+
+fn verify(hash: &str, check: Check, plaintext: &[u8]) -> Option<bool> {
+    match check {
+        Check::Hex(algorithm) => Some(hex::decode(hash).ok()? == algorithm.digest(plaintext)),
+        Check::Mysql41 => {
+            let digest = HashAlgorithm::Sha1.digest(&HashAlgorithm::Sha1.digest(plaintext));
+            Some(hex::decode(&hash[1..]).ok()? == digest)
+        }
+        Check::Ldap { algorithm, salted } => {
+            let encoded = &hash[hash.find('}')? + 1..];
+            let Ok(decoded) = base64::engine::general_purpose::STANDARD.decode(encoded) else {
+                return Some(false);
+            };
+            if decoded.len() < algorithm.digest_len()
+                || (!salted && decoded.len() != algorithm.digest_len())
+            {
+                return Some(false);
+            }
+            let (digest, salt) = decoded.split_at(algorithm.digest_len());
+            Some(digest == algorithm.digest(&[plaintext, salt].concat()))
+        }
+        Check::Unsupported => None,
+    }
+}
+
+/// Guess which algorithm produced a hash from its length, alphabet and prefix, as JSON
+/// `{"candidates": [{"name", "confidence", "verified"}]}` ranked from most to least likely.
+/// With `plaintext` every candidate implemented by this crate is computed and `verified`
+/// tells whether it matches, matches are ranked first. `verified` is null otherwise.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct IdentifyHash {
+    plaintext: Option<String>,
+}
+
+impl Operation for IdentifyHash {
+    fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
+        let mut output = vec![];
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        let hash = core::str::from_utf8(input)?.trim();
+        let mut candidates: Vec<(Candidate, Option<bool>)> = identify(hash)
+            .into_iter()
+            .map(|candidate| {
+                let verified = self
+                    .plaintext
+                    .as_ref()
+                    .and_then(|plaintext| verify(hash, candidate.check, plaintext.as_bytes()));
+                (candidate, verified)
+            })
+            .collect();
+        // Stable, so candidates keep the table order within the same rank
+        candidates.sort_by_key(|(candidate, verified)| {
+            let rank = match verified {
+                Some(true) => 0,
+                None => 1,
+                Some(false) => 2,
+            };
+            (rank, candidate.confidence)
+        });
+
+        let candidates: Vec<Value> = candidates
+            .iter()
+            .map(|(candidate, verified)| {
+                json!({
+                    "name": candidate.name,
+                    "confidence": candidate.confidence.name(),
+                    "verified": verified,
+                })
+            })
+            .collect();
+        out.extend_from_slice(json!({ "candidates": candidates }).to_string().as_bytes());
+        Ok(())
+    }
+}
+
+impl IdentifyHash {
+    pub const fn new(plaintext: Option<String>) -> Self {
+        IdentifyHash { plaintext }
+    }
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for IdentifyHash {
+    const ID: &'static str = "identify_hash";
+    const NAME: &'static str = "Identify Hash";
+    const CATEGORY: Category = Category::Analysis;
+    const DESCRIPTION: &'static str =
+        "Ranked candidate algorithms of a hash as JSON, optionally verified against a plaintext";
+
+    fn options_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "plaintext": { "type": ["string", "null"] }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identify(plaintext: Option<&str>, input: &str) -> Vec<(String, String, Value)> {
+        let output =
+            IdentifyHash::new(plaintext.map(Into::into)).execute(input.as_bytes()).unwrap();
+        let output: Value = serde_json::from_slice(&output).unwrap();
+        output["candidates"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|candidate| {
+                (
+                    candidate["name"].as_str().unwrap().into(),
+                    candidate["confidence"].as_str().unwrap().into(),
+                    candidate["verified"].clone(),
+                )
+            })
+            .collect()
+    }
+
+    fn names(plaintext: Option<&str>, input: &str) -> Vec<String> {
+        identify(plaintext, input).into_iter().map(|(name, _, _)| name).collect()
+    }
+
+    #[test]
+    fn identify_hex_digests() {
+        assert_eq!(names(None, "0d14b5c7855daa1c6f5bf7e4e1fbd762\n"), ["md5", "ntlm", "md4", "lm"]);
+        assert_eq!(names(None, "8846F7EAEE8FB117AD06BDD830B7586C"), ["ntlm", "md5", "md4", "lm"]);
+        assert_eq!(names(None, &"a".repeat(64))[..2], ["sha256", "blake3"]);
+        assert_eq!(names(None, &"b".repeat(128))[0], "sha512");
+        assert_eq!(
+            identify(None, &"c".repeat(40))[0],
+            ("sha1".into(), "medium".into(), Value::Null)
+        );
+        assert!(names(None, "xyz").is_empty());
+        assert!(names(None, &"a".repeat(33)).is_empty());
+    }
+
+    #[test]
+    fn identify_structured_hashes() {
+        let bcrypt = "$2b$12$R9h/cIPz0gi.URNNX3kh2OPST9/PgBkqquzi.Ss7KIUgO2t0jWMUW";
+        assert_eq!(identify(None, bcrypt), [("bcrypt".into(), "high".into(), Value::Null)]);
+        assert_eq!(names(None, "$argon2id$v=19$m=65536,t=3,p=4$c2FsdA$aGFzaA"), ["argon2id"]);
+        assert_eq!(names(None, "$argon2i$v=19$m=4096,t=3,p=1$c2FsdA$aGFzaA"), ["argon2i"]);
+        assert_eq!(names(None, "$6$rounds=5000$salt$hash"), ["sha512crypt"]);
+        assert_eq!(names(None, "{SSHA}uJDd0BIdJ9Z7yDCZNWdgYeb33+cBAgME"), ["ldap_ssha"]);
+        assert_eq!(names(None, "*2470C0C06DEE42FD1618BB99005ADCA2EC9D1E19"), ["mysql41"]);
+        let lm = "aad3b435b51404eeaad3b435b51404ee";
+        let nt = "8846f7eaee8fb117ad06bdd830b7586c";
+        assert_eq!(names(None, &format!("{lm}:{nt}")), ["ntlm"]);
+        assert_eq!(names(None, &format!("Administrator:500:{lm}:{nt}:::")), ["ntlm"]);
+    }
+
+    #[test]
+    fn identify_verifies_plaintext() {
+        let sha256 = "f85b3ccfdb7d51eb7cd37395e7dd423b05da972de615671b147c38a8a4a8642e";
+        let candidates = identify(Some("irongate"), sha256);
+        assert_eq!(candidates[0], ("sha256".into(), "medium".into(), Value::Bool(true)));
+        assert_eq!(candidates.last().unwrap().2, Value::Bool(false));
+        assert_eq!(candidates.last().unwrap().0, "blake3");
+
+        let md5 = "0D14B5C7855DAA1C6F5BF7E4E1FBD762";
+        assert_eq!(identify(Some("irongate"), md5)[0].2, Value::Bool(true));
+        assert_eq!(identify(Some("wrong"), md5)[0].0, "ntlm");
+        assert_eq!(identify(Some("wrong"), md5)[0].2, Value::Null);
+    }
+
+    #[test]
+    fn identify_verifies_salted_schemes() {
+        let ssha = "{SSHA}uJDd0BIdJ9Z7yDCZNWdgYeb33+cBAgME";
+        assert_eq!(identify(Some("secret"), ssha)[0].2, Value::Bool(true));
+        assert_eq!(identify(Some("public"), ssha)[0].2, Value::Bool(false));
+        let sha256 = "{sha256}K7gNU3sdo+OL0wNhqoVWhr3g6s1xYv72ol/pe/Unols=";
+        assert_eq!(identify(Some("secret"), sha256)[0].2, Value::Bool(true));
+        let mysql = "*2470C0C06DEE42FD1618BB99005ADCA2EC9D1E19";
+        assert_eq!(identify(Some("password"), mysql)[0].2, Value::Bool(true));
+        assert_eq!(identify(Some("secret"), "$2b$12$abc")[0].2, Value::Null);
+        assert_eq!(identify(Some("secret"), "{SSHA}not base64!")[0].2, Value::Bool(false));
+    }
+}
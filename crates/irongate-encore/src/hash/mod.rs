@@ -1,9 +1,23 @@
+#[cfg(all(feature = "analysis", feature = "encode"))]
+mod identify;
+mod multi;
+
+use alloc::vec;
+use alloc::vec::Vec;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "registry")]
+use serde_json::{json, Value};
 use sha1::{Digest as Sha1Digest, Sha1};
 
+#[cfg(all(feature = "analysis", feature = "encode"))]
+pub use identify::*;
+pub use multi::*;
+
 use crate::Operation;
 use crate::OperationError;
+#[cfg(feature = "registry")]
+use crate::{Category, OperationInfo};
 
 #[derive(Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -11,7 +25,14 @@ pub struct Md5Hash {}
 
 impl Operation for Md5Hash {
     fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
-        Ok(md5::compute(input).to_vec())
+        let mut output = vec![];
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        out.extend_from_slice(&md5::compute(input).0);
+        Ok(())
     }
 }
 
@@ -27,9 +48,14 @@ pub struct Sha1Hash {}
 
 impl Operation for Sha1Hash {
     fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
-        let mut hasher = Sha1::new();
-        hasher.update(input);
-        Ok(hasher.finalize().to_vec())
+        let mut output = vec![];
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        out.extend_from_slice(&Sha1::digest(input));
+        Ok(())
     }
 }
 
@@ -62,33 +88,104 @@ pub enum Sha2Version {
 
 impl Operation for Sha2Hash {
     fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
-        let hash_vec = match self.version {
-            Sha2Version::Sha224 => {
-                let mut hasher = sha2::Sha224::new();
-                hasher.update(input);
-                hasher.finalize().to_vec()
-            }
-            Sha2Version::Sha256 => {
-                let mut hasher = sha2::Sha256::new();
-                hasher.update(input);
-                hasher.finalize().to_vec()
-            }
-            Sha2Version::Sha384 => {
-                let mut hasher = sha2::Sha384::new();
-                hasher.update(input);
-                hasher.finalize().to_vec()
-            }
-            Sha2Version::Sha512 => {
-                let mut hasher = sha2::Sha512::new();
-                hasher.update(input);
-                hasher.finalize().to_vec()
-            }
-        };
-        Ok(hash_vec)
+        let mut output = vec![];
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        match self.version {
+            Sha2Version::Sha224 => out.extend_from_slice(&sha2::Sha224::digest(input)),
+            Sha2Version::Sha256 => out.extend_from_slice(&sha2::Sha256::digest(input)),
+            Sha2Version::Sha384 => out.extend_from_slice(&sha2::Sha384::digest(input)),
+            Sha2Version::Sha512 => out.extend_from_slice(&sha2::Sha512::digest(input)),
+        }
+        Ok(())
     }
 }
 
-#[cfg(test)]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Blake3Hash {}
+
+impl Operation for Blake3Hash {
+    fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
+        let mut output = vec![];
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        out.extend_from_slice(blake3::hash(input).as_bytes());
+        Ok(())
+    }
+}
+
+impl Blake3Hash {
+    pub const fn new() -> Self {
+        Blake3Hash {}
+    }
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for Md5Hash {
+    const ID: &'static str = "md5_hash";
+    const NAME: &'static str = "MD5";
+    const CATEGORY: Category = Category::Hash;
+    const DESCRIPTION: &'static str = "Raw MD5 digest";
+
+    fn options_schema() -> Value {
+        json!({ "type": "object", "properties": {} })
+    }
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for Sha1Hash {
+    const ID: &'static str = "sha1_hash";
+    const NAME: &'static str = "SHA-1";
+    const CATEGORY: Category = Category::Hash;
+    const DESCRIPTION: &'static str = "Raw SHA-1 digest";
+
+    fn options_schema() -> Value {
+        json!({ "type": "object", "properties": {} })
+    }
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for Sha2Hash {
+    const ID: &'static str = "sha2_hash";
+    const NAME: &'static str = "SHA-2";
+    const CATEGORY: Category = Category::Hash;
+    const DESCRIPTION: &'static str = "Raw SHA-224, SHA-256, SHA-384 or SHA-512 digest";
+
+    fn options_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "version": {
+                    "type": "string",
+                    "enum": ["Sha224", "Sha256", "Sha384", "Sha512"],
+                    "default": "Sha256"
+                }
+            },
+            "required": ["version"]
+        })
+    }
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for Blake3Hash {
+    const ID: &'static str = "blake3_hash";
+    const NAME: &'static str = "BLAKE3";
+    const CATEGORY: Category = Category::Hash;
+    const DESCRIPTION: &'static str = "Raw 256-bit BLAKE3 digest";
+
+    fn options_schema() -> Value {
+        json!({ "type": "object", "properties": {} })
+    }
+}
+
+#[cfg(all(test, feature = "encode"))]
 mod tests {
     use super::*;
     use crate::HexEncode;
@@ -102,4 +199,12 @@ mod tests {
         let hex_result = encode.execute(&res).unwrap();
         assert_eq!(hex_result, b"0d14b5c7855daa1c6f5bf7e4e1fbd762")
     }
+
+    #[test]
+    fn hash_blake3() {
+        let res = Blake3Hash::new().execute(b"").unwrap();
+        let encode = HexEncode::new(crate::HexFormat::Lower, None, None);
+        let hex_result = encode.execute(&res).unwrap();
+        assert_eq!(hex_result, b"af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262")
+    }
 }
@@ -0,0 +1,369 @@
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "registry")]
+use serde_json::{json, Value};
+use sha1::{Digest, Sha1};
+
+use crate::Operation;
+use crate::OperationError;
+#[cfg(feature = "registry")]
+use crate::{Category, OperationInfo};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum HashAlgorithm {
+    Md5,
+    Sha1,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+    Blake3,
+}
+
+impl HashAlgorithm {
+    pub const fn name(self) -> &'static str {
+        match self {
+            HashAlgorithm::Md5 => "md5",
+            HashAlgorithm::Sha1 => "sha1",
+            HashAlgorithm::Sha224 => "sha224",
+            HashAlgorithm::Sha256 => "sha256",
+            HashAlgorithm::Sha384 => "sha384",
+            HashAlgorithm::Sha512 => "sha512",
+            HashAlgorithm::Blake3 => "blake3",
+        }
+    }
+
+    /// Size of the digest in bytes
+    pub const fn digest_len(self) -> usize {
+        match self {
+            HashAlgorithm::Md5 => 16,
+            HashAlgorithm::Sha1 => 20,
+            HashAlgorithm::Sha224 => 28,
+            HashAlgorithm::Sha256 | HashAlgorithm::Blake3 => 32,
+            HashAlgorithm::Sha384 => 48,
+            HashAlgorithm::Sha512 => 64,
+        }
+    }
+
+    /// Digest of `input` with this algorithm alone
+    pub fn digest(self, input: &[u8]) -> Vec<u8> {
+        let mut hasher = MultiHasher::new(&[self]);
+        hasher.update(input);
+        hasher.finalize().pop().map(|(_, digest)| digest).unwrap_or_default()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum MultiHashFormat {
+    /// `{"md5": "<hex>", ...}` in the order of the algorithms
+    Json,
+    /// Raw digests back to back, as described by `MultiHash::layout`
+    Concatenated,
+}
+
+/// Compute several digests of the input in a single pass
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MultiHash {
+    algorithms: Vec<HashAlgorithm>,
+    format: MultiHashFormat,
+}
+
+impl Operation for MultiHash {
+    fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
+        let mut output = vec![];
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        let mut hasher = self.hasher()?;
+        hasher.update(input);
+        self.finalize_into(hasher, out);
+        Ok(())
+    }
+}
+
+impl MultiHash {
+    pub const fn new(algorithms: Vec<HashAlgorithm>, format: MultiHashFormat) -> Self {
+        MultiHash { algorithms, format }
+    }
+
+    /// Incremental hasher for input that does not fit in memory, finish it with `finalize_into`
+    pub fn hasher(&self) -> Result<MultiHasher, OperationError> {
+        if self.algorithms.is_empty() {
+            return Err(OperationError::InvalidOptions("No hash algorithm selected".into()));
+        }
+        for (index, algorithm) in self.algorithms.iter().enumerate() {
+            if self.algorithms[..index].contains(algorithm) {
+                return Err(OperationError::InvalidOptions(format!(
+                    "Hash algorithm {} selected twice",
+                    algorithm.name()
+                )));
+            }
+        }
+        Ok(MultiHasher::new(&self.algorithms))
+    }
+
+    /// Append the digests of `hasher` to `out` in the format of this operation
+    pub fn finalize_into(&self, hasher: MultiHasher, out: &mut Vec<u8>) {
+        let digests = hasher.finalize();
+        match self.format {
+            MultiHashFormat::Concatenated => {
+                for (_, digest) in digests {
+                    out.extend_from_slice(&digest);
+                }
+            }
+            MultiHashFormat::Json => {
+                out.push(b'{');
+                for (index, (algorithm, digest)) in digests.iter().enumerate() {
+                    if index > 0 {
+                        out.push(b',');
+                    }
+                    out.push(b'"');
+                    out.extend_from_slice(algorithm.name().as_bytes());
+                    out.extend_from_slice(b"\":\"");
+                    for byte in digest {
+                        out.extend_from_slice(&[
+                            HEX[(byte >> 4) as usize],
+                            HEX[(byte & 0xf) as usize],
+                        ]);
+                    }
+                    out.push(b'"');
+                }
+                out.push(b'}');
+            }
+        }
+    }
+
+    /// Position of each digest in the concatenated output
+    pub fn layout(&self) -> Vec<(HashAlgorithm, Range<usize>)> {
+        let mut offset = 0;
+        self.algorithms
+            .iter()
+            .map(|algorithm| {
+                let range = offset..offset + algorithm.digest_len();
+                offset = range.end;
+                (*algorithm, range)
+            })
+            .collect()
+    }
+}
+
+const HEX: &[u8; 16] = b"0123456789abcdef";
+
+/// Input handed to each digest at once by `MultiHasher::update`, small enough to stay in L2
+const CHUNK_SIZE: usize = 64 * 1024;
+
+#[derive(Clone)]
+enum State {
+    Md5(md5::Context),
+    Sha1(Sha1),
+    Sha224(sha2::Sha224),
+    Sha256(sha2::Sha256),
+    Sha384(sha2::Sha384),
+    Sha512(sha2::Sha512),
+    Blake3(Box<blake3::Hasher>),
+}
+
+/// Streaming counterpart of `MultiHash`, every chunk is fed to all digests at once
+#[derive(Clone)]
+pub struct MultiHasher {
+    states: Vec<(HashAlgorithm, State)>,
+}
+
+impl MultiHasher {
+    pub fn new(algorithms: &[HashAlgorithm]) -> Self {
+        let states = algorithms
+            .iter()
+            .map(|algorithm| {
+                let state = match algorithm {
+                    HashAlgorithm::Md5 => State::Md5(md5::Context::new()),
+                    HashAlgorithm::Sha1 => State::Sha1(Sha1::new()),
+                    HashAlgorithm::Sha224 => State::Sha224(sha2::Sha224::new()),
+                    HashAlgorithm::Sha256 => State::Sha256(sha2::Sha256::new()),
+                    HashAlgorithm::Sha384 => State::Sha384(sha2::Sha384::new()),
+                    HashAlgorithm::Sha512 => State::Sha512(sha2::Sha512::new()),
+                    HashAlgorithm::Blake3 => State::Blake3(Box::default()),
+                };
+                (*algorithm, state)
+            })
+            .collect();
+        MultiHasher { states }
+    }
+
+    /// Feeds `data` to every state a chunk at a time, so each chunk is read from cache by all the
+    /// digests instead of streaming the whole input from memory once per algorithm
+    pub fn update(&mut self, data: &[u8]) {
+        for chunk in data.chunks(CHUNK_SIZE) {
+            for (_, state) in &mut self.states {
+                match state {
+                    State::Md5(context) => context.consume(chunk),
+                    State::Sha1(hasher) => hasher.update(chunk),
+                    State::Sha224(hasher) => hasher.update(chunk),
+                    State::Sha256(hasher) => hasher.update(chunk),
+                    State::Sha384(hasher) => hasher.update(chunk),
+                    State::Sha512(hasher) => hasher.update(chunk),
+                    State::Blake3(hasher) => {
+                        hasher.update(chunk);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Digests in the order the algorithms were given
+    pub fn finalize(self) -> Vec<(HashAlgorithm, Vec<u8>)> {
+        self.states
+            .into_iter()
+            .map(|(algorithm, state)| {
+                let digest = match state {
+                    State::Md5(context) => context.compute().0.to_vec(),
+                    State::Sha1(hasher) => hasher.finalize().to_vec(),
+                    State::Sha224(hasher) => hasher.finalize().to_vec(),
+                    State::Sha256(hasher) => hasher.finalize().to_vec(),
+                    State::Sha384(hasher) => hasher.finalize().to_vec(),
+                    State::Sha512(hasher) => hasher.finalize().to_vec(),
+                    State::Blake3(hasher) => hasher.finalize().as_bytes().to_vec(),
+                };
+                (algorithm, digest)
+            })
+            .collect()
+    }
+}
+
+/// Allows `std::io::copy(&mut file, &mut hasher)`
+#[cfg(feature = "std")]
+impl std::io::Write for MultiHasher {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for MultiHash {
+    const ID: &'static str = "multi_hash";
+    const NAME: &'static str = "Multi Hash";
+    const CATEGORY: Category = Category::Hash;
+    const DESCRIPTION: &'static str = "Several digests of the input computed in a single pass";
+
+    fn options_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "algorithms": {
+                    "type": "array",
+                    "items": {
+                        "type": "string",
+                        "enum": ["md5", "sha1", "sha224", "sha256", "sha384", "sha512", "blake3"]
+                    },
+                    "minItems": 1,
+                    "uniqueItems": true,
+                    "default": ["md5", "sha1", "sha256"]
+                },
+                "format": { "type": "string", "enum": ["json", "concatenated"], "default": "json" }
+            },
+            "required": ["algorithms", "format"]
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::{Blake3Hash, Md5Hash, Sha1Hash, Sha2Hash, Sha2Version};
+
+    const ALL: [HashAlgorithm; 7] = [
+        HashAlgorithm::Md5,
+        HashAlgorithm::Sha1,
+        HashAlgorithm::Sha224,
+        HashAlgorithm::Sha256,
+        HashAlgorithm::Sha384,
+        HashAlgorithm::Sha512,
+        HashAlgorithm::Blake3,
+    ];
+
+    fn single(algorithm: HashAlgorithm, input: &[u8]) -> Vec<u8> {
+        let operation: Box<dyn Operation> = match algorithm {
+            HashAlgorithm::Md5 => Box::new(Md5Hash::new()),
+            HashAlgorithm::Sha1 => Box::new(Sha1Hash::new()),
+            HashAlgorithm::Sha224 => Box::new(Sha2Hash::new(Sha2Version::Sha224)),
+            HashAlgorithm::Sha256 => Box::new(Sha2Hash::new(Sha2Version::Sha256)),
+            HashAlgorithm::Sha384 => Box::new(Sha2Hash::new(Sha2Version::Sha384)),
+            HashAlgorithm::Sha512 => Box::new(Sha2Hash::new(Sha2Version::Sha512)),
+            HashAlgorithm::Blake3 => Box::new(Blake3Hash::new()),
+        };
+        operation.execute(input).unwrap()
+    }
+
+    #[test]
+    fn multi_hash_json() {
+        let hasher =
+            MultiHash::new(vec![HashAlgorithm::Md5, HashAlgorithm::Sha1], MultiHashFormat::Json);
+        let expected = r#"{"md5":"0d14b5c7855daa1c6f5bf7e4e1fbd762","sha1":"#;
+        let output = String::from_utf8(hasher.execute(b"irongate").unwrap()).unwrap();
+        assert!(output.starts_with(expected));
+        assert_eq!(output.len(), expected.len() + 40 + 3);
+    }
+
+    #[test]
+    fn multi_hash_layout() {
+        let hasher = MultiHash::new(ALL.to_vec(), MultiHashFormat::Concatenated);
+        let output = hasher.execute(b"irongate").unwrap();
+        let layout = hasher.layout();
+        assert_eq!(layout.last().unwrap().1.end, output.len());
+        for (algorithm, range) in layout {
+            assert_eq!(range.len(), algorithm.digest_len());
+            assert_eq!(output[range], single(algorithm, b"irongate"));
+            assert_eq!(algorithm.digest(b"irongate"), single(algorithm, b"irongate"));
+        }
+    }
+
+    #[test]
+    fn multi_hash_spans_chunks() {
+        let input: Vec<u8> = (0..2 * CHUNK_SIZE + 1).map(|index| index as u8).collect();
+        let hasher = MultiHash::new(ALL.to_vec(), MultiHashFormat::Concatenated);
+        let output = hasher.execute(&input).unwrap();
+        for (algorithm, range) in hasher.layout() {
+            assert_eq!(output[range], single(algorithm, &input));
+        }
+    }
+
+    #[test]
+    fn multi_hash_invalid_options() {
+        assert!(MultiHash::new(vec![], MultiHashFormat::Json).execute(b"").is_err());
+        let twice = vec![HashAlgorithm::Sha1, HashAlgorithm::Md5, HashAlgorithm::Sha1];
+        assert!(MultiHash::new(twice, MultiHashFormat::Json).execute(b"").is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn multi_hasher_streaming(input: Vec<u8>, split in 0usize..1024) {
+            let hasher = MultiHash::new(ALL.to_vec(), MultiHashFormat::Concatenated);
+            let (head, tail) = input.split_at(split.min(input.len()));
+            let mut streaming = hasher.hasher().unwrap();
+            streaming.update(head);
+            streaming.update(tail);
+            let mut output = vec![];
+            hasher.finalize_into(streaming, &mut output);
+            prop_assert_eq!(output, hasher.execute(&input).unwrap());
+        }
+    }
+}
@@ -1,11 +1,32 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![allow(clippy::new_without_default)]
 
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+#[cfg(feature = "analysis")]
+pub mod analysis;
+#[cfg(feature = "certificate")]
+pub mod certificate;
 #[cfg(feature = "encode")]
 pub mod encoding;
 pub mod errors;
 #[cfg(feature = "hash")]
 pub mod hash;
+#[cfg(feature = "registry")]
+pub mod registry;
+#[cfg(feature = "text")]
+pub mod text;
+#[cfg(feature = "time")]
+pub mod timestamp;
 
+#[cfg(feature = "analysis")]
+pub use analysis::*;
+#[cfg(feature = "certificate")]
+pub use certificate::asn1::*;
+#[cfg(feature = "certificate")]
+pub use certificate::x509::*;
 #[cfg(feature = "encode")]
 pub use encoding::base32hex::*;
 #[cfg(feature = "encode")]
@@ -15,13 +36,37 @@ pub use encoding::hex::*;
 #[cfg(feature = "encode")]
 pub use encoding::html::*;
 #[cfg(feature = "encode")]
+pub use encoding::pem::*;
+#[cfg(feature = "encode")]
 pub use encoding::url::*;
 
 pub use errors::OperationError;
 
 #[cfg(feature = "hash")]
 pub use hash::*;
+#[cfg(feature = "registry")]
+pub use registry::{Category, OperationInfo};
+#[cfg(feature = "text")]
+pub use text::*;
+#[cfg(feature = "time")]
+pub use timestamp::*;
 
 pub trait Operation {
     fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError>;
+
+    /// Append the result to `out` so callers can reuse a single buffer, `out`
+    /// keeps its original content when an error is returned
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        out.extend_from_slice(&self.execute(input)?);
+        Ok(())
+    }
+}
+
+/// An operation that can be undone by another operation
+pub trait Reversible: Operation {
+    type Inverse: Operation;
+
+    /// Build the inverse operation configured with matching options, such that
+    /// `self.inverse().execute(&self.execute(input)?)` yields `input` back
+    fn inverse(&self) -> Self::Inverse;
 }
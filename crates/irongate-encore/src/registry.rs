@@ -0,0 +1,259 @@
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::ToString;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::Operation;
+use crate::OperationError;
+
+pub type BoxedOperation = Box<dyn Operation + Send + Sync>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Category {
+    Encoding,
+    Hash,
+    Compression,
+    Certificate,
+    Time,
+    Analysis,
+    Text,
+}
+
+/// Static metadata describing an operation to front-ends
+pub trait OperationInfo: Operation {
+    /// Stable identifier used to look the operation up in the registry
+    const ID: &'static str;
+    const NAME: &'static str;
+    const CATEGORY: Category;
+    const DESCRIPTION: &'static str;
+
+    /// JSON schema of the options accepted by the registry for this operation
+    fn options_schema() -> Value;
+
+    /// Other spellings of option keys accepted by the registry, as `(alias, key)` pairs
+    const OPTION_ALIASES: &'static [(&'static str, &'static str)] = &[];
+}
+
+pub struct OperationDescriptor {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub category: Category,
+    pub description: &'static str,
+    options_schema: fn() -> Value,
+    build: fn(Value) -> Result<BoxedOperation, OperationError>,
+}
+
+fn build_operation<T>(options: Value) -> Result<BoxedOperation, OperationError>
+where
+    T: OperationInfo + DeserializeOwned + Send + Sync + 'static,
+{
+    let mut options = match options {
+        Value::Null => Value::Object(Default::default()),
+        options => options,
+    };
+    if let Value::Object(fields) = &mut options {
+        for (alias, key) in T::OPTION_ALIASES {
+            if let Some(value) = fields.remove(*alias) {
+                if fields.insert(key.to_string(), value).is_some() {
+                    return Err(OperationError::InvalidOptions(format!(
+                        "{}: duplicate option `{key}`",
+                        T::ID
+                    )));
+                }
+            }
+        }
+        // The option structs accept unknown fields, so a misspelled key would silently fall back
+        // to its default instead of failing
+        let schema = T::options_schema();
+        if let Some(key) = fields.keys().find(|key| schema["properties"].get(key).is_none()) {
+            return Err(OperationError::InvalidOptions(format!(
+                "{}: unknown option `{key}`",
+                T::ID
+            )));
+        }
+    }
+    let operation: T = serde_json::from_value(options)
+        .map_err(|err| OperationError::InvalidOptions(format!("{}: {err}", T::ID)))?;
+    Ok(Box::new(operation))
+}
+
+impl OperationDescriptor {
+    pub const fn of<T>() -> Self
+    where
+        T: OperationInfo + DeserializeOwned + Send + Sync + 'static,
+    {
+        OperationDescriptor {
+            id: T::ID,
+            name: T::NAME,
+            category: T::CATEGORY,
+            description: T::DESCRIPTION,
+            options_schema: T::options_schema,
+            build: build_operation::<T>,
+        }
+    }
+
+    pub fn options_schema(&self) -> Value {
+        (self.options_schema)()
+    }
+
+    /// Instantiate the operation, `null` is accepted as an empty options object
+    pub fn build(&self, options: Value) -> Result<BoxedOperation, OperationError> {
+        (self.build)(options)
+    }
+}
+
+static OPERATIONS: &[OperationDescriptor] = &[
+    #[cfg(feature = "encode")]
+    OperationDescriptor::of::<crate::Base32HexEncode>(),
+    #[cfg(feature = "encode")]
+    OperationDescriptor::of::<crate::Base32HexDecode>(),
+    #[cfg(feature = "encode")]
+    OperationDescriptor::of::<crate::Base64Encode>(),
+    #[cfg(feature = "encode")]
+    OperationDescriptor::of::<crate::Base64Decode>(),
+    #[cfg(feature = "encode")]
+    OperationDescriptor::of::<crate::HexEncode>(),
+    #[cfg(feature = "encode")]
+    OperationDescriptor::of::<crate::HexDecode>(),
+    #[cfg(feature = "encode")]
+    OperationDescriptor::of::<crate::HtmlEncode>(),
+    #[cfg(feature = "encode")]
+    OperationDescriptor::of::<crate::HtmlDecode>(),
+    #[cfg(feature = "encode")]
+    OperationDescriptor::of::<crate::PemEncode>(),
+    #[cfg(feature = "encode")]
+    OperationDescriptor::of::<crate::PemDecode>(),
+    #[cfg(feature = "encode")]
+    OperationDescriptor::of::<crate::UrlEncode>(),
+    #[cfg(feature = "encode")]
+    OperationDescriptor::of::<crate::UrlDecode>(),
+    #[cfg(feature = "hash")]
+    OperationDescriptor::of::<crate::Md5Hash>(),
+    #[cfg(feature = "hash")]
+    OperationDescriptor::of::<crate::Sha1Hash>(),
+    #[cfg(feature = "hash")]
+    OperationDescriptor::of::<crate::Sha2Hash>(),
+    #[cfg(feature = "hash")]
+    OperationDescriptor::of::<crate::Blake3Hash>(),
+    #[cfg(feature = "hash")]
+    OperationDescriptor::of::<crate::MultiHash>(),
+    #[cfg(feature = "certificate")]
+    OperationDescriptor::of::<crate::Asn1Decode>(),
+    #[cfg(feature = "certificate")]
+    OperationDescriptor::of::<crate::X509Decode>(),
+    #[cfg(feature = "time")]
+    OperationDescriptor::of::<crate::TimestampConvert>(),
+    #[cfg(feature = "analysis")]
+    OperationDescriptor::of::<crate::Analyze>(),
+    #[cfg(all(feature = "analysis", feature = "encode", feature = "hash"))]
+    OperationDescriptor::of::<crate::IdentifyHash>(),
+    #[cfg(feature = "text")]
+    OperationDescriptor::of::<crate::RegexReplace>(),
+    #[cfg(feature = "text")]
+    OperationDescriptor::of::<crate::RegexExtract>(),
+    #[cfg(feature = "text")]
+    OperationDescriptor::of::<crate::ChangeCase>(),
+    #[cfg(feature = "text")]
+    OperationDescriptor::of::<crate::Reverse>(),
+    #[cfg(feature = "text")]
+    OperationDescriptor::of::<crate::SortLines>(),
+    #[cfg(feature = "text")]
+    OperationDescriptor::of::<crate::UniqueLines>(),
+    #[cfg(feature = "text")]
+    OperationDescriptor::of::<crate::Trim>(),
+    #[cfg(feature = "text")]
+    OperationDescriptor::of::<crate::Split>(),
+    #[cfg(feature = "text")]
+    OperationDescriptor::of::<crate::Join>(),
+    #[cfg(feature = "text")]
+    OperationDescriptor::of::<crate::AddLineNumbers>(),
+    #[cfg(feature = "text")]
+    OperationDescriptor::of::<crate::RemoveLineNumbers>(),
+];
+
+/// All built-in operations enabled by the current feature set
+pub fn operations() -> &'static [OperationDescriptor] {
+    OPERATIONS
+}
+
+pub fn find(id: &str) -> Option<&'static OperationDescriptor> {
+    OPERATIONS.iter().find(|descriptor| descriptor.id == id)
+}
+
+pub fn create(id: &str, options: Value) -> Result<BoxedOperation, OperationError> {
+    find(id).ok_or_else(|| OperationError::UnknownOperation(id.to_string()))?.build(options)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn registry_ids_are_unique() {
+        let ids: HashSet<_> = operations().iter().map(|descriptor| descriptor.id).collect();
+        assert_eq!(ids.len(), operations().len());
+    }
+
+    #[test]
+    fn registry_schemas_are_objects() {
+        for descriptor in operations() {
+            let schema = descriptor.options_schema();
+            assert_eq!(schema["type"], "object", "{}", descriptor.id);
+        }
+    }
+
+    #[test]
+    fn registry_create_with_options() {
+        let operation = create("base64_encode", json!({ "format": "url", "pad": false })).unwrap();
+        assert_eq!(operation.execute(b"\xfb\xff").unwrap(), b"-_8");
+    }
+
+    #[test]
+    fn registry_create_without_options() {
+        let operation = create("md5_hash", Value::Null).unwrap();
+        assert_eq!(operation.execute(b"irongate").unwrap().len(), 16);
+    }
+
+    #[test]
+    fn registry_create_with_defaults() {
+        let encode = create("url_encode", json!({})).unwrap();
+        assert_eq!(encode.execute("a b@é".as_bytes()).unwrap(), "a b@é".as_bytes());
+        let encode = create("url_encode", json!({ "non_ascii": true })).unwrap();
+        assert_eq!(encode.execute("a b@é".as_bytes()).unwrap(), b"a b@%C3%A9");
+        let encode = create("url_encode", json!({ "nonAscii": true })).unwrap();
+        assert_eq!(encode.execute("a b@é".as_bytes()).unwrap(), b"a b@%C3%A9");
+        let err = create("url_encode", json!({ "non_ascii": true, "nonAscii": true })).err();
+        assert!(matches!(err, Some(OperationError::InvalidOptions(_))));
+        let encode = create("url_encode", json!({ "charset": " " })).unwrap();
+        assert_eq!(encode.execute(b"a b").unwrap(), b"a%20b");
+    }
+
+    #[test]
+    fn registry_unknown_operation() {
+        let err = create("rot13", Value::Null).err().unwrap();
+        assert!(matches!(err, OperationError::UnknownOperation(_)));
+    }
+
+    #[test]
+    fn registry_invalid_options() {
+        let err = create("sha2_hash", json!({ "version": "Sha1" })).err().unwrap();
+        assert!(matches!(err, OperationError::InvalidOptions(_)));
+    }
+
+    #[test]
+    fn registry_unknown_option() {
+        let options = json!({ "format": "url", "pad": false, "padding": true });
+        let err = create("base64_encode", options).err().unwrap();
+        assert!(
+            matches!(err, OperationError::InvalidOptions(message) if message.ends_with("`padding`"))
+        );
+        assert!(create("md5_hash", json!({ "format": "hex" })).is_err());
+        assert!(create("md5_hash", json!({})).is_ok());
+    }
+}
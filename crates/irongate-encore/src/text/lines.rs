@@ -0,0 +1,474 @@
+use alloc::collections::BTreeSet;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use bstr::ByteSlice;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "registry")]
+use serde_json::{json, Value};
+
+use super::{join_lines, split_lines};
+use crate::Operation;
+use crate::OperationError;
+use crate::Reversible;
+#[cfg(feature = "registry")]
+use crate::{Category, OperationInfo};
+
+/// Sort lines by their bytes, which orders valid UTF-8 by code point
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SortLines {
+    reverse: Option<bool>,
+    /// Drop repeated lines after sorting
+    unique: Option<bool>,
+}
+
+impl Operation for SortLines {
+    fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
+        let mut output = vec![];
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        let (mut lines, trailing) = split_lines(input);
+        lines.sort_unstable();
+        if self.unique.unwrap_or_default() {
+            lines.dedup();
+        }
+        if self.reverse.unwrap_or_default() {
+            lines.reverse();
+        }
+        join_lines(lines, trailing, out);
+        Ok(())
+    }
+}
+
+impl SortLines {
+    pub const fn new(reverse: Option<bool>, unique: Option<bool>) -> Self {
+        SortLines { reverse, unique }
+    }
+}
+
+/// Drop repeated lines, keeping the first occurrence of each line in place
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UniqueLines {}
+
+impl Operation for UniqueLines {
+    fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
+        let mut output = vec![];
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        let (lines, trailing) = split_lines(input);
+        let mut seen = BTreeSet::new();
+        join_lines(lines.into_iter().filter(|line| seen.insert(*line)), trailing, out);
+        Ok(())
+    }
+}
+
+impl UniqueLines {
+    pub const fn new() -> Self {
+        UniqueLines {}
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum TrimSide {
+    Both,
+    Start,
+    End,
+}
+
+/// Strip Unicode whitespace from the input, or from every line with `lines`
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Trim {
+    side: Option<TrimSide>,
+    lines: Option<bool>,
+}
+
+impl Operation for Trim {
+    fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
+        let mut output = vec![];
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        let side = self.side.unwrap_or(TrimSide::Both);
+        if self.lines.unwrap_or_default() {
+            let (lines, trailing) = split_lines(input);
+            join_lines(lines.into_iter().map(|line| trim(line, side)), trailing, out);
+        } else {
+            out.extend_from_slice(trim(input, side));
+        }
+        Ok(())
+    }
+}
+
+fn trim(bytes: &[u8], side: TrimSide) -> &[u8] {
+    match side {
+        TrimSide::Both => bytes.trim(),
+        TrimSide::Start => bytes.trim_start(),
+        TrimSide::End => bytes.trim_end(),
+    }
+}
+
+impl Trim {
+    pub const fn new(side: Option<TrimSide>, lines: Option<bool>) -> Self {
+        Trim { side, lines }
+    }
+}
+
+/// Put every part of the input separated by `delimiter` on its own line
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Split {
+    delimiter: String,
+}
+
+impl Operation for Split {
+    fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
+        let mut output = vec![];
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        if self.delimiter.is_empty() {
+            return Err(OperationError::InvalidOptions("delimiter must not be empty".into()));
+        }
+        join_lines(input.split_str(&self.delimiter), false, out);
+        Ok(())
+    }
+}
+
+impl Split {
+    pub const fn new(delimiter: String) -> Self {
+        Split { delimiter }
+    }
+}
+
+/// Join the lines of the input with `delimiter`, a final newline is dropped rather than joined
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Join {
+    delimiter: String,
+}
+
+impl Operation for Join {
+    fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
+        let mut output = vec![];
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        let (lines, _) = split_lines(input);
+        for (index, line) in lines.into_iter().enumerate() {
+            if index != 0 {
+                out.extend_from_slice(self.delimiter.as_bytes());
+            }
+            out.extend_from_slice(line);
+        }
+        Ok(())
+    }
+}
+
+impl Join {
+    pub const fn new(delimiter: String) -> Self {
+        Join { delimiter }
+    }
+}
+
+/// Prefix every line with its number and a space, counting from `start` (1 by default)
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AddLineNumbers {
+    start: Option<usize>,
+}
+
+impl Operation for AddLineNumbers {
+    fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
+        let mut output = vec![];
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        let (lines, trailing) = split_lines(input);
+        let start = self.start.unwrap_or(1);
+        if start.checked_add(lines.len().saturating_sub(1)).is_none() {
+            return Err(OperationError::InvalidOptions(format!(
+                "add_line_numbers: start {start} overflows on line {}",
+                lines.len()
+            )));
+        }
+        for (index, line) in lines.into_iter().enumerate() {
+            if index != 0 {
+                out.push(b'\n');
+            }
+            out.extend_from_slice((start + index).to_string().as_bytes());
+            out.push(b' ');
+            out.extend_from_slice(line);
+        }
+        if trailing {
+            out.push(b'\n');
+        }
+        Ok(())
+    }
+}
+
+impl AddLineNumbers {
+    pub const fn new(start: Option<usize>) -> Self {
+        AddLineNumbers { start }
+    }
+}
+
+/// Strip a leading line number from every line: optional spaces or tabs, digits, then a single
+/// space, tab, `:`, `.`, `)`, `|`, `-` or `,`, or the end of the line. Other lines are kept.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RemoveLineNumbers {}
+
+impl Operation for RemoveLineNumbers {
+    fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
+        let mut output = vec![];
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        let (lines, trailing) = split_lines(input);
+        join_lines(lines.into_iter().map(strip_line_number), trailing, out);
+        Ok(())
+    }
+}
+
+fn strip_line_number(line: &[u8]) -> &[u8] {
+    let number = line.trim_start_with(|char| char == ' ' || char == '\t');
+    let rest = number.trim_start_with(|char| char.is_ascii_digit());
+    if rest.len() == number.len() {
+        return line;
+    }
+    match rest.split_first() {
+        None => rest,
+        Some((b' ' | b'\t' | b':' | b'.' | b')' | b'|' | b'-' | b',', rest)) => rest,
+        Some(_) => line,
+    }
+}
+
+impl RemoveLineNumbers {
+    pub const fn new() -> Self {
+        RemoveLineNumbers {}
+    }
+}
+
+/// Exactly one number and space is stripped from every line, so any input survives the round trip
+impl Reversible for AddLineNumbers {
+    type Inverse = RemoveLineNumbers;
+
+    fn inverse(&self) -> RemoveLineNumbers {
+        RemoveLineNumbers::new()
+    }
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for SortLines {
+    const ID: &'static str = "sort_lines";
+    const NAME: &'static str = "Sort Lines";
+    const CATEGORY: Category = Category::Text;
+    const DESCRIPTION: &'static str = "Sort lines, optionally reversed and without duplicates";
+
+    fn options_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "reverse": { "type": ["boolean", "null"], "default": false },
+                "unique": { "type": ["boolean", "null"], "default": false }
+            }
+        })
+    }
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for UniqueLines {
+    const ID: &'static str = "unique_lines";
+    const NAME: &'static str = "Unique Lines";
+    const CATEGORY: Category = Category::Text;
+    const DESCRIPTION: &'static str = "Remove duplicate lines, keeping the original order";
+
+    fn options_schema() -> Value {
+        json!({ "type": "object", "properties": {} })
+    }
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for Trim {
+    const ID: &'static str = "trim";
+    const NAME: &'static str = "Trim";
+    const CATEGORY: Category = Category::Text;
+    const DESCRIPTION: &'static str = "Strip leading and/or trailing whitespace";
+
+    fn options_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "side": {
+                    "type": ["string", "null"],
+                    "enum": ["both", "start", "end", null],
+                    "default": "both"
+                },
+                "lines": { "type": ["boolean", "null"], "default": false }
+            }
+        })
+    }
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for Split {
+    const ID: &'static str = "split";
+    const NAME: &'static str = "Split";
+    const CATEGORY: Category = Category::Text;
+    const DESCRIPTION: &'static str = "Split the input on a delimiter into lines";
+
+    fn options_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "delimiter": { "type": "string", "minLength": 1 }
+            },
+            "required": ["delimiter"]
+        })
+    }
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for Join {
+    const ID: &'static str = "join";
+    const NAME: &'static str = "Join";
+    const CATEGORY: Category = Category::Text;
+    const DESCRIPTION: &'static str = "Join lines with a delimiter";
+
+    fn options_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "delimiter": { "type": "string" }
+            },
+            "required": ["delimiter"]
+        })
+    }
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for AddLineNumbers {
+    const ID: &'static str = "add_line_numbers";
+    const NAME: &'static str = "Add Line Numbers";
+    const CATEGORY: Category = Category::Text;
+    const DESCRIPTION: &'static str = "Prefix every line with its line number";
+
+    fn options_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "start": { "type": ["integer", "null"], "minimum": 0, "default": 1 }
+            }
+        })
+    }
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for RemoveLineNumbers {
+    const ID: &'static str = "remove_line_numbers";
+    const NAME: &'static str = "Remove Line Numbers";
+    const CATEGORY: Category = Category::Text;
+    const DESCRIPTION: &'static str = "Strip leading line numbers from every line";
+
+    fn options_schema() -> Value {
+        json!({ "type": "object", "properties": {} })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn sort_lines() {
+        let input = b"b\nc\na\nb\n";
+        assert_eq!(SortLines::new(None, None).execute(input).unwrap(), b"a\nb\nb\nc\n");
+        assert_eq!(SortLines::new(Some(true), Some(true)).execute(input).unwrap(), b"c\nb\na\n");
+        assert_eq!(
+            SortLines::new(None, None).execute(b"\xff\n\xc3\xa9\na").unwrap(),
+            b"a\n\xc3\xa9\n\xff"
+        );
+    }
+
+    #[test]
+    fn unique_lines() {
+        let input = b"b\na\nb\n\xff\na\n\xff";
+        assert_eq!(UniqueLines::new().execute(input).unwrap(), b"b\na\n\xff");
+    }
+
+    #[test]
+    fn trim() {
+        let input = " \u{a0}a b\t\n  c \n".as_bytes();
+        assert_eq!(Trim::new(None, None).execute(input).unwrap(), b"a b\t\n  c");
+        assert_eq!(Trim::new(None, Some(true)).execute(input).unwrap(), b"a b\nc\n");
+        assert_eq!(
+            Trim::new(Some(TrimSide::Start), Some(true)).execute(input).unwrap(),
+            b"a b\t\nc \n"
+        );
+        assert_eq!(Trim::new(Some(TrimSide::End), None).execute(b" \xff ").unwrap(), b" \xff");
+    }
+
+    #[test]
+    fn split_join() {
+        assert_eq!(Split::new(", ".into()).execute(b"a, b,c, \xff").unwrap(), b"a\nb,c\n\xff");
+        assert_eq!(Join::new("|".into()).execute(b"a\nb\n\nc\n").unwrap(), b"a|b||c");
+        assert!(matches!(
+            Split::new("".into()).execute(b"a"),
+            Err(OperationError::InvalidOptions(_))
+        ));
+    }
+
+    #[test]
+    fn line_numbers() {
+        let numbered = AddLineNumbers::new(None).execute(b"a\n\n  b\n").unwrap();
+        assert_eq!(numbered, b"1 a\n2 \n3   b\n");
+        assert_eq!(AddLineNumbers::new(Some(9)).execute(b"a\nb").unwrap(), b"9 a\n10 b");
+        let last = AddLineNumbers::new(Some(usize::MAX));
+        assert_eq!(last.execute(b"a").unwrap(), format!("{} a", usize::MAX).as_bytes());
+        assert!(matches!(last.execute(b"a\nb"), Err(OperationError::InvalidOptions(_))));
+        assert_eq!(
+            RemoveLineNumbers::new().execute(b"  1:a\n2) b\n3\n10\tc\nd\n42abc").unwrap(),
+            b"a\n b\n\nc\nd\n42abc"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn line_numbers_round_trip(input: Vec<u8>) {
+            let operation = AddLineNumbers::new(None);
+            let numbered = operation.execute(&input).unwrap();
+            prop_assert_eq!(operation.inverse().execute(&numbered).unwrap(), input);
+        }
+
+        #[test]
+        fn split_join_round_trip(input in "([a-z]*,)*[a-z]+") {
+            let lines = Split::new(",".into()).execute(input.as_bytes()).unwrap();
+            prop_assert_eq!(Join::new(",".into()).execute(&lines).unwrap(), input.as_bytes());
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use alloc::vec::Vec;
+
+mod lines;
+mod pattern;
+mod transform;
+
+pub use lines::*;
+pub use pattern::*;
+pub use transform::*;
+
+/// Lines of `input` without their `\n` terminator, and whether the last line had one
+fn split_lines(input: &[u8]) -> (Vec<&[u8]>, bool) {
+    if input.is_empty() {
+        return (Vec::new(), false);
+    }
+    let (body, trailing) = match input.strip_suffix(b"\n") {
+        Some(body) => (body, true),
+        None => (input, false),
+    };
+    (body.split(|byte| *byte == b'\n').collect(), trailing)
+}
+
+fn join_lines<'a>(lines: impl IntoIterator<Item = &'a [u8]>, trailing: bool, out: &mut Vec<u8>) {
+    for (index, line) in lines.into_iter().enumerate() {
+        if index != 0 {
+            out.push(b'\n');
+        }
+        out.extend_from_slice(line);
+    }
+    if trailing {
+        out.push(b'\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+
+    #[test]
+    fn split_lines_terminators() {
+        assert_eq!(split_lines(b""), (vec![], false));
+        assert_eq!(split_lines(b"\n"), (vec![&b""[..]], true));
+        assert_eq!(split_lines(b"a\n\nb"), (vec![&b"a"[..], b"", b"b"], false));
+        assert_eq!(split_lines(b"a\r\nb\n"), (vec![&b"a\r"[..], b"b"], true));
+    }
+
+    #[test]
+    fn join_lines_round_trip() {
+        for input in [&b""[..], b"\n", b"a\n\nb", b"a\n\xffb\n"] {
+            let (lines, trailing) = split_lines(input);
+            let mut output = vec![];
+            join_lines(lines, trailing, &mut output);
+            assert_eq!(output, input);
+        }
+    }
+}
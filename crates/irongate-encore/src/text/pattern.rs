@@ -0,0 +1,307 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use regex::bytes::{Captures, Regex, RegexBuilder};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "registry")]
+use serde_json::{json, Value};
+
+use crate::Operation;
+use crate::OperationError;
+#[cfg(feature = "registry")]
+use crate::{Category, OperationInfo};
+
+fn build_regex(pattern: &str, case_insensitive: Option<bool>) -> Result<Regex, String> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(case_insensitive.unwrap_or_default())
+        .build()
+        .map_err(|err| err.to_string())
+}
+
+/// Replace matches of a regular expression, `$1` or `${name}` in the replacement expand to
+/// capture groups. Matching runs on bytes, so invalid UTF-8 outside of matches is kept as is.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(try_from = "RegexReplaceOptions", into = "RegexReplaceOptions")
+)]
+pub struct RegexReplace {
+    options: RegexReplaceOptions,
+    regex: Regex,
+}
+
+/// The pattern is compiled once, when the operation is built
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct RegexReplaceOptions {
+    pattern: String,
+    replacement: String,
+    case_insensitive: Option<bool>,
+    /// Replace every match (the default) or only the first one
+    global: Option<bool>,
+}
+
+impl TryFrom<RegexReplaceOptions> for RegexReplace {
+    type Error = String;
+
+    fn try_from(options: RegexReplaceOptions) -> Result<Self, String> {
+        let regex = build_regex(&options.pattern, options.case_insensitive)?;
+        Ok(RegexReplace { options, regex })
+    }
+}
+
+impl From<RegexReplace> for RegexReplaceOptions {
+    fn from(operation: RegexReplace) -> Self {
+        operation.options
+    }
+}
+
+impl Operation for RegexReplace {
+    fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
+        let mut output = vec![];
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        let limit = if self.options.global.unwrap_or(true) { 0 } else { 1 };
+        let replacement = self.options.replacement.as_bytes();
+        out.extend_from_slice(&self.regex.replacen(input, limit, replacement));
+        Ok(())
+    }
+}
+
+impl RegexReplace {
+    /// Fails with `InvalidOptions` when the pattern does not compile
+    pub fn new(
+        pattern: String,
+        replacement: String,
+        case_insensitive: Option<bool>,
+        global: Option<bool>,
+    ) -> Result<Self, OperationError> {
+        RegexReplaceOptions { pattern, replacement, case_insensitive, global }
+            .try_into()
+            .map_err(OperationError::InvalidOptions)
+    }
+}
+
+/// Extract every match of a regular expression, one per `delimiter` (a newline by default).
+/// With `group` only that capture group is written, otherwise patterns with capture groups
+/// write all of their groups separated by tabs and patterns without write the whole match.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(try_from = "RegexExtractOptions", into = "RegexExtractOptions")
+)]
+pub struct RegexExtract {
+    options: RegexExtractOptions,
+    regex: Regex,
+    groups: Vec<usize>,
+}
+
+/// The pattern is compiled and `group` checked once, when the operation is built
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct RegexExtractOptions {
+    pattern: String,
+    case_insensitive: Option<bool>,
+    group: Option<usize>,
+    delimiter: Option<String>,
+}
+
+impl TryFrom<RegexExtractOptions> for RegexExtract {
+    type Error = String;
+
+    fn try_from(options: RegexExtractOptions) -> Result<Self, String> {
+        let regex = build_regex(&options.pattern, options.case_insensitive)?;
+        let groups = match options.group {
+            Some(group) if group >= regex.captures_len() => {
+                return Err(format!("group {group} does not exist in the pattern"));
+            }
+            Some(group) => vec![group],
+            None if regex.captures_len() == 1 => vec![0],
+            None => (1..regex.captures_len()).collect(),
+        };
+        Ok(RegexExtract { options, regex, groups })
+    }
+}
+
+impl From<RegexExtract> for RegexExtractOptions {
+    fn from(operation: RegexExtract) -> Self {
+        operation.options
+    }
+}
+
+impl Operation for RegexExtract {
+    fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
+        let mut output = vec![];
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        let delimiter = self.options.delimiter.as_deref().unwrap_or("\n").as_bytes();
+
+        for (index, captures) in self.regex.captures_iter(input).enumerate() {
+            if index != 0 {
+                out.extend_from_slice(delimiter);
+            }
+            extend_with_groups(out, &captures, &self.groups);
+        }
+        Ok(())
+    }
+}
+
+/// Groups that did not participate in the match are written as empty strings
+fn extend_with_groups(output: &mut Vec<u8>, captures: &Captures, groups: &[usize]) {
+    for (index, group) in groups.iter().enumerate() {
+        if index != 0 {
+            output.push(b'\t');
+        }
+        if let Some(group) = captures.get(*group) {
+            output.extend_from_slice(group.as_bytes());
+        }
+    }
+}
+
+impl RegexExtract {
+    /// Fails with `InvalidOptions` when the pattern does not compile or has no such `group`
+    pub fn new(
+        pattern: String,
+        case_insensitive: Option<bool>,
+        group: Option<usize>,
+        delimiter: Option<String>,
+    ) -> Result<Self, OperationError> {
+        RegexExtractOptions { pattern, case_insensitive, group, delimiter }
+            .try_into()
+            .map_err(OperationError::InvalidOptions)
+    }
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for RegexReplace {
+    const ID: &'static str = "regex_replace";
+    const NAME: &'static str = "Regex Replace";
+    const CATEGORY: Category = Category::Text;
+    const DESCRIPTION: &'static str = "Replace regular expression matches, with capture groups";
+
+    fn options_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "pattern": { "type": "string" },
+                "replacement": { "type": "string" },
+                "case_insensitive": { "type": ["boolean", "null"], "default": false },
+                "global": { "type": ["boolean", "null"], "default": true }
+            },
+            "required": ["pattern", "replacement"]
+        })
+    }
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for RegexExtract {
+    const ID: &'static str = "regex_extract";
+    const NAME: &'static str = "Regex Extract";
+    const CATEGORY: Category = Category::Text;
+    const DESCRIPTION: &'static str = "Extract all regular expression matches or capture groups";
+
+    fn options_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "pattern": { "type": "string" },
+                "case_insensitive": { "type": ["boolean", "null"], "default": false },
+                "group": { "type": ["integer", "null"], "minimum": 0 },
+                "delimiter": { "type": ["string", "null"], "default": "\n" }
+            },
+            "required": ["pattern"]
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replace(pattern: &str, replacement: &str, global: Option<bool>, input: &[u8]) -> Vec<u8> {
+        let operation =
+            RegexReplace::new(pattern.into(), replacement.into(), None, global).unwrap();
+        operation.execute(input).unwrap()
+    }
+
+    fn extract(pattern: &str, group: Option<usize>, input: &[u8]) -> Vec<u8> {
+        RegexExtract::new(pattern.into(), None, group, None).unwrap().execute(input).unwrap()
+    }
+
+    #[test]
+    fn regex_replace() {
+        assert_eq!(replace(r"\d+", "#", None, b"a1b22c333"), b"a#b#c#");
+        assert_eq!(replace(r"\d+", "#", Some(false), b"a1b22c333"), b"a#b22c333");
+        assert_eq!(replace(r"(\w+)@(\w+)", "$2 at ${1}", None, b"root@host"), b"host at root");
+    }
+
+    #[test]
+    fn regex_replace_case_insensitive() {
+        let operation = RegexReplace::new("gate".into(), "GATE".into(), Some(true), None).unwrap();
+        assert_eq!(operation.execute(b"IronGate").unwrap(), b"IronGATE");
+    }
+
+    #[test]
+    fn regex_replace_keeps_invalid_utf8() {
+        assert_eq!(replace("é", "e", None, b"\xffcaf\xc3\xa9\xfe"), b"\xffcafe\xfe");
+    }
+
+    #[test]
+    fn regex_extract() {
+        assert_eq!(extract(r"\d+", None, b"a1b22c333"), b"1\n22\n333");
+        assert_eq!(extract(r"(\w+)=(\w*)", None, b"a=1&b=&c=3"), b"a\t1\nb\t\nc\t3");
+        assert_eq!(extract(r"(\w+)=(\w*)", Some(2), b"a=1&b=&c=3"), b"1\n\n3");
+        assert_eq!(extract(r"(\w+)=(\w*)", Some(0), b"a=1&c=3"), b"a=1\nc=3");
+        assert_eq!(extract(r"x(y)?", None, b"x xy"), b"\ny");
+        assert_eq!(extract(r"\d", None, b"none"), b"");
+    }
+
+    #[test]
+    fn regex_extract_delimiter() {
+        let operation = RegexExtract::new(r"\d".into(), None, None, Some(", ".into())).unwrap();
+        assert_eq!(operation.execute(b"1a2\xff3").unwrap(), b"1, 2, 3");
+    }
+
+    #[test]
+    fn regex_invalid_options() {
+        assert!(matches!(
+            RegexExtract::new("(a".into(), None, None, None),
+            Err(OperationError::InvalidOptions(_))
+        ));
+        assert!(matches!(
+            RegexExtract::new("(a)".into(), None, Some(2), None),
+            Err(OperationError::InvalidOptions(_))
+        ));
+        assert!(matches!(
+            RegexReplace::new("(a".into(), "b".into(), None, None),
+            Err(OperationError::InvalidOptions(_))
+        ));
+    }
+
+    #[cfg(feature = "registry")]
+    #[test]
+    fn regex_registry_rejects_invalid_patterns() {
+        use serde_json::json;
+
+        let options = json!({ "pattern": "(a", "replacement": "b" });
+        let err = crate::registry::create("regex_replace", options).err().unwrap();
+        assert!(
+            matches!(err, OperationError::InvalidOptions(message) if message.starts_with("regex_replace: regex parse error"))
+        );
+        let options = json!({ "pattern": "(a)", "group": 2 });
+        assert!(crate::registry::create("regex_extract", options).is_err());
+        let options = json!({ "pattern": r"(\w+)=(\w*)", "group": 2, "delimiter": "," });
+        let operation = crate::registry::create("regex_extract", options).unwrap();
+        assert_eq!(operation.execute(b"a=1&b=2").unwrap(), b"1,2");
+    }
+}
@@ -0,0 +1,231 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use bstr::ByteSlice;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "registry")]
+use serde_json::{json, Value};
+
+use super::{join_lines, split_lines};
+use crate::Operation;
+use crate::OperationError;
+#[cfg(feature = "registry")]
+use crate::{Category, OperationInfo};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum TextCase {
+    Upper,
+    Lower,
+    /// Uppercase the first letter of every word and lowercase the rest
+    Title,
+    /// Swap the case of every letter
+    Swap,
+}
+
+/// Change the case of the input, invalid UTF-8 sequences are copied unchanged
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ChangeCase {
+    case: TextCase,
+}
+
+impl Operation for ChangeCase {
+    fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
+        let mut output = vec![];
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        out.reserve(input.len());
+        match self.case {
+            TextCase::Upper => input.to_uppercase_into(out),
+            TextCase::Lower => input.to_lowercase_into(out),
+            TextCase::Title => {
+                let mut in_word = false;
+                map_chars(input, out, |char, out| {
+                    if in_word {
+                        extend_with_chars(out, char.to_lowercase());
+                    } else {
+                        extend_with_chars(out, char.to_uppercase());
+                    }
+                    in_word = char.is_alphanumeric() || char == '\'';
+                });
+            }
+            TextCase::Swap => map_chars(input, out, |char, out| {
+                if char.is_uppercase() {
+                    extend_with_chars(out, char.to_lowercase());
+                } else {
+                    extend_with_chars(out, char.to_uppercase());
+                }
+            }),
+        }
+        Ok(())
+    }
+}
+
+impl ChangeCase {
+    pub const fn new(case: TextCase) -> Self {
+        ChangeCase { case }
+    }
+}
+
+/// Call `map` for every valid character and copy invalid UTF-8 sequences as is
+fn map_chars(input: &[u8], out: &mut Vec<u8>, mut map: impl FnMut(char, &mut Vec<u8>)) {
+    for (start, end, char) in input.char_indices() {
+        let bytes = &input[start..end];
+        if char == char::REPLACEMENT_CHARACTER && bytes != "\u{FFFD}".as_bytes() {
+            out.extend_from_slice(bytes);
+        } else {
+            map(char, out);
+        }
+    }
+}
+
+fn extend_with_chars(out: &mut Vec<u8>, chars: impl Iterator<Item = char>) {
+    let mut buffer = [0; 4];
+    chars.for_each(|char| out.extend_from_slice(char.encode_utf8(&mut buffer).as_bytes()));
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ReverseUnit {
+    Byte,
+    /// Grapheme clusters, so combining marks and emoji sequences stay intact
+    Character,
+    /// Lines, a trailing newline stays at the end. Without one, a leading empty line reads as
+    /// the trailing newline once reversed, so `"\na"` becomes `"a\n"` and does not round-trip
+    Line,
+}
+
+/// Reverse the input by `unit`, invalid UTF-8 sequences are kept whole when reversing characters
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Reverse {
+    unit: ReverseUnit,
+}
+
+impl Operation for Reverse {
+    fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
+        let mut output = vec![];
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        out.reserve(input.len());
+        match self.unit {
+            ReverseUnit::Byte => out.extend(input.iter().rev()),
+            ReverseUnit::Character => {
+                let graphemes: Vec<_> = input.grapheme_indices().collect();
+                for (start, stop, _) in graphemes.into_iter().rev() {
+                    out.extend_from_slice(&input[start..stop]);
+                }
+            }
+            ReverseUnit::Line => {
+                let (lines, trailing) = split_lines(input);
+                join_lines(lines.into_iter().rev(), trailing, out);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Reverse {
+    pub const fn new(unit: ReverseUnit) -> Self {
+        Reverse { unit }
+    }
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for ChangeCase {
+    const ID: &'static str = "change_case";
+    const NAME: &'static str = "Change Case";
+    const CATEGORY: Category = Category::Text;
+    const DESCRIPTION: &'static str = "Convert text to upper, lower, title or swapped case";
+
+    fn options_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "case": { "type": "string", "enum": ["upper", "lower", "title", "swap"] }
+            },
+            "required": ["case"]
+        })
+    }
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for Reverse {
+    const ID: &'static str = "reverse";
+    const NAME: &'static str = "Reverse";
+    const CATEGORY: Category = Category::Text;
+    const DESCRIPTION: &'static str = "Reverse the order of bytes, characters or lines";
+
+    fn options_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "unit": { "type": "string", "enum": ["byte", "character", "line"] }
+            },
+            "required": ["unit"]
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn change_case(case: TextCase, input: &[u8]) -> Vec<u8> {
+        ChangeCase::new(case).execute(input).unwrap()
+    }
+
+    fn reverse(unit: ReverseUnit, input: &[u8]) -> Vec<u8> {
+        Reverse::new(unit).execute(input).unwrap()
+    }
+
+    #[test]
+    fn change_case_modes() {
+        let input = "hello WORLD, it's ÉTÉ".as_bytes();
+        assert_eq!(change_case(TextCase::Upper, input), "HELLO WORLD, IT'S ÉTÉ".as_bytes());
+        assert_eq!(change_case(TextCase::Lower, input), "hello world, it's été".as_bytes());
+        assert_eq!(change_case(TextCase::Title, input), "Hello World, It's Été".as_bytes());
+        assert_eq!(change_case(TextCase::Swap, input), "HELLO world, IT'S été".as_bytes());
+    }
+
+    #[test]
+    fn change_case_keeps_invalid_utf8() {
+        let input = b"ab\xffcd\xe2\x82";
+        assert_eq!(change_case(TextCase::Upper, input), b"AB\xffCD\xe2\x82");
+        assert_eq!(change_case(TextCase::Title, input), b"Ab\xffcd\xe2\x82");
+        assert_eq!(change_case(TextCase::Swap, "\u{FFFD}a".as_bytes()), "\u{FFFD}A".as_bytes());
+    }
+
+    #[test]
+    fn reverse_units() {
+        let input = "ab\ne\u{301}🇫🇷\n".as_bytes();
+        assert_eq!(reverse(ReverseUnit::Byte, b"ab\xff"), b"\xffba");
+        assert_eq!(reverse(ReverseUnit::Character, input), "\n🇫🇷e\u{301}\nba".as_bytes());
+        assert_eq!(reverse(ReverseUnit::Line, input), "e\u{301}🇫🇷\nab\n".as_bytes());
+        assert_eq!(reverse(ReverseUnit::Character, b"a\xffb"), b"b\xffa");
+        assert_eq!(reverse(ReverseUnit::Line, b"a\nb"), b"b\na");
+        // The leading empty line turns into the trailing newline
+        assert_eq!(reverse(ReverseUnit::Line, b"\n\0"), b"\0\n");
+        assert_eq!(reverse(ReverseUnit::Line, b"\0\n"), b"\0\n");
+    }
+
+    proptest! {
+        #[test]
+        fn reverse_twice_is_identity(input: Vec<u8>) {
+            prop_assert_eq!(reverse(ReverseUnit::Byte, &reverse(ReverseUnit::Byte, &input)), input.clone());
+            prop_assume!(input.ends_with(b"\n") || !input.starts_with(b"\n"));
+            prop_assert_eq!(reverse(ReverseUnit::Line, &reverse(ReverseUnit::Line, &input)), input);
+        }
+    }
+}
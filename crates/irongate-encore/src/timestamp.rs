@@ -0,0 +1,444 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use chrono::{
+    DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, SecondsFormat, TimeZone, Utc,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "registry")]
+use serde_json::{json, Value};
+
+use crate::Operation;
+use crate::OperationError;
+#[cfg(feature = "registry")]
+use crate::{Category, OperationInfo};
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+
+/// Seconds between the Unix epoch and the epochs of the other formats
+const FILETIME_EPOCH: i128 = -11_644_473_600;
+const DOTNET_EPOCH: i128 = -62_135_596_800;
+const COCOA_EPOCH: i128 = 978_307_200;
+const GREGORIAN_EPOCH: i128 = -12_219_292_800;
+
+/// Twitter's epoch, Discord ids use 1420070400000
+const SNOWFLAKE_EPOCH: u64 = 1_288_834_974_657;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum TimestampFormat {
+    UnixSeconds,
+    UnixMilliseconds,
+    UnixMicroseconds,
+    UnixNanoseconds,
+    /// 100ns intervals since 1601-01-01
+    WindowsFiletime,
+    /// 100ns intervals since 0001-01-01
+    DotnetTicks,
+    /// Seconds since 2001-01-01, as used by Apple's `NSDate`
+    Cocoa,
+    /// Microseconds since 1601-01-01, as used by Chrome and WebKit
+    Webkit,
+    /// Time of a version 1, 6 or 7 UUID, read only
+    Uuid,
+    /// Time of a ULID, read only
+    Ulid,
+    /// Time of a Snowflake id, read only
+    Snowflake,
+    Iso8601,
+    Rfc2822,
+}
+
+/// Convert a textual timestamp between formats. Date strings without an offset are read in
+/// `timezone`, which is also the offset used for date string output (UTC by default).
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TimestampConvert {
+    from: TimestampFormat,
+    to: TimestampFormat,
+    timezone: Option<String>,
+    snowflake_epoch: Option<u64>,
+}
+
+impl Operation for TimestampConvert {
+    fn execute(&self, input: &[u8]) -> Result<Vec<u8>, OperationError> {
+        let mut output = vec![];
+        self.execute_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn execute_into(&self, input: &[u8], out: &mut Vec<u8>) -> Result<(), OperationError> {
+        let timezone = parse_timezone(self.timezone.as_deref().unwrap_or_default())?;
+        let input = core::str::from_utf8(input)?.trim();
+        let time = self.read(input, &timezone)?;
+        let output = self.write(time, &timezone)?;
+        out.extend_from_slice(output.as_bytes());
+        Ok(())
+    }
+}
+
+impl TimestampConvert {
+    pub const fn new(
+        from: TimestampFormat,
+        to: TimestampFormat,
+        timezone: Option<String>,
+        snowflake_epoch: Option<u64>,
+    ) -> Self {
+        TimestampConvert { from, to, timezone, snowflake_epoch }
+    }
+
+    fn read(&self, input: &str, timezone: &FixedOffset) -> Result<DateTime<Utc>, OperationError> {
+        let nanos = match self.from {
+            TimestampFormat::UnixSeconds => parse_decimal(input)?,
+            TimestampFormat::UnixMilliseconds => parse_integer(input)? * 1_000_000,
+            TimestampFormat::UnixMicroseconds => parse_integer(input)? * 1_000,
+            TimestampFormat::UnixNanoseconds => parse_integer(input)?,
+            TimestampFormat::WindowsFiletime => {
+                parse_integer(input)? * 100 + FILETIME_EPOCH * NANOS_PER_SECOND
+            }
+            TimestampFormat::DotnetTicks => {
+                parse_integer(input)? * 100 + DOTNET_EPOCH * NANOS_PER_SECOND
+            }
+            TimestampFormat::Cocoa => parse_decimal(input)? + COCOA_EPOCH * NANOS_PER_SECOND,
+            TimestampFormat::Webkit => {
+                parse_integer(input)? * 1_000 + FILETIME_EPOCH * NANOS_PER_SECOND
+            }
+            TimestampFormat::Uuid => uuid_nanos(input)?,
+            TimestampFormat::Ulid => ulid_nanos(input)?,
+            TimestampFormat::Snowflake => {
+                let id: u64 = input.parse().map_err(|_| invalid("Snowflake id"))?;
+                let epoch = self.snowflake_epoch.unwrap_or(SNOWFLAKE_EPOCH);
+                let millis = (id >> 22)
+                    .checked_add(epoch)
+                    .ok_or_else(|| invalid("timestamp out of range"))?;
+                i128::from(millis) * 1_000_000
+            }
+            TimestampFormat::Iso8601 => return parse_iso8601(input, timezone),
+            TimestampFormat::Rfc2822 => {
+                return DateTime::parse_from_rfc2822(input)
+                    .map(|time| time.to_utc())
+                    .map_err(|_| invalid("RFC 2822 date"))
+            }
+        };
+        let seconds = i64::try_from(nanos.div_euclid(NANOS_PER_SECOND))
+            .map_err(|_| invalid("timestamp out of range"))?;
+        DateTime::from_timestamp(seconds, nanos.rem_euclid(NANOS_PER_SECOND) as u32)
+            .ok_or_else(|| invalid("timestamp out of range"))
+    }
+
+    fn write(&self, time: DateTime<Utc>, timezone: &FixedOffset) -> Result<String, OperationError> {
+        let nanos = i128::from(time.timestamp()) * NANOS_PER_SECOND
+            + i128::from(time.timestamp_subsec_nanos());
+        let output = match self.to {
+            TimestampFormat::UnixSeconds => format_decimal(nanos),
+            TimestampFormat::UnixMilliseconds => nanos.div_euclid(1_000_000).to_string(),
+            TimestampFormat::UnixMicroseconds => nanos.div_euclid(1_000).to_string(),
+            TimestampFormat::UnixNanoseconds => nanos.to_string(),
+            TimestampFormat::WindowsFiletime => {
+                (nanos - FILETIME_EPOCH * NANOS_PER_SECOND).div_euclid(100).to_string()
+            }
+            TimestampFormat::DotnetTicks => {
+                (nanos - DOTNET_EPOCH * NANOS_PER_SECOND).div_euclid(100).to_string()
+            }
+            TimestampFormat::Cocoa => format_decimal(nanos - COCOA_EPOCH * NANOS_PER_SECOND),
+            TimestampFormat::Webkit => {
+                (nanos - FILETIME_EPOCH * NANOS_PER_SECOND).div_euclid(1_000).to_string()
+            }
+            TimestampFormat::Uuid | TimestampFormat::Ulid | TimestampFormat::Snowflake => {
+                return Err(OperationError::InvalidOptions(format!(
+                    "{:?} timestamps can only be read",
+                    self.to
+                )))
+            }
+            TimestampFormat::Iso8601 => {
+                time.with_timezone(timezone).to_rfc3339_opts(SecondsFormat::AutoSi, true)
+            }
+            TimestampFormat::Rfc2822 => {
+                let time = time.with_timezone(timezone);
+                // chrono panics on years RFC 2822 cannot represent
+                if !(0..=9999).contains(&time.year()) {
+                    return Err(OperationError::DecodeError {
+                        message: format!(
+                            "Year {} cannot be written as an RFC 2822 date",
+                            time.year()
+                        ),
+                        offset: None,
+                    });
+                }
+                time.to_rfc2822()
+            }
+        };
+        Ok(output)
+    }
+}
+
+fn invalid(what: &str) -> OperationError {
+    OperationError::DecodeError { message: format!("Invalid {what}"), offset: None }
+}
+
+/// `UTC`, `Z` or an offset such as `+05:30`, `-0800` or `+02`
+fn parse_timezone(timezone: &str) -> Result<FixedOffset, OperationError> {
+    let error = || OperationError::InvalidOptions(format!("Invalid timezone: {timezone}"));
+    if timezone.is_empty() || timezone.eq_ignore_ascii_case("utc") || timezone == "Z" {
+        return Ok(FixedOffset::east_opt(0).unwrap());
+    }
+    let (sign, offset) = match timezone.as_bytes()[0] {
+        b'+' => (1, &timezone[1..]),
+        b'-' => (-1, &timezone[1..]),
+        _ => return Err(error()),
+    };
+    let digits: String = offset.chars().filter(|ch| *ch != ':').collect();
+    if !matches!(digits.len(), 2 | 4) || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(error());
+    }
+    let hours: i32 = digits[..2].parse().map_err(|_| error())?;
+    let minutes: i32 =
+        digits.get(2..).filter(|m| !m.is_empty()).unwrap_or("0").parse().map_err(|_| error())?;
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60)).ok_or_else(error)
+}
+
+/// Integer, in decimal or with a `0x` prefix in hex as FILETIME values are often written
+fn parse_integer(input: &str) -> Result<i128, OperationError> {
+    let parsed = match input.strip_prefix("0x").or_else(|| input.strip_prefix("0X")) {
+        Some(hex) => i128::from_str_radix(hex, 16),
+        None => input.parse(),
+    };
+    let value = parsed.map_err(|_| invalid("integer timestamp"))?;
+    // Keeps the conversions to nanoseconds away from overflows
+    if value.unsigned_abs() > 1 << 100 {
+        return Err(invalid("timestamp out of range"));
+    }
+    Ok(value)
+}
+
+/// Seconds with an optional fraction, as nanoseconds
+fn parse_decimal(input: &str) -> Result<i128, OperationError> {
+    let error = || invalid("decimal timestamp");
+    let (negative, unsigned) = match input.strip_prefix('-') {
+        Some(unsigned) => (true, unsigned),
+        None => (false, input.strip_prefix('+').unwrap_or(input)),
+    };
+    let (whole, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+    if whole.is_empty() && fraction.is_empty()
+        || !whole.bytes().chain(fraction.bytes()).all(|byte| byte.is_ascii_digit())
+        || whole.len() > 20
+    {
+        return Err(error());
+    }
+    let whole: i128 = if whole.is_empty() { 0 } else { whole.parse().map_err(|_| error())? };
+    let fraction = fraction.bytes().chain(core::iter::repeat(b'0')).take(9);
+    let fraction = fraction.fold(0, |value, digit| value * 10 + i128::from(digit - b'0'));
+    let nanos = whole * NANOS_PER_SECOND + fraction;
+    Ok(if negative { -nanos } else { nanos })
+}
+
+fn format_decimal(nanos: i128) -> String {
+    let sign = if nanos < 0 { "-" } else { "" };
+    let nanos = nanos.unsigned_abs();
+    let (seconds, fraction) = (nanos / 1_000_000_000, nanos % 1_000_000_000);
+    if fraction == 0 {
+        format!("{sign}{seconds}")
+    } else {
+        let fraction = format!("{fraction:09}");
+        format!("{sign}{seconds}.{}", fraction.trim_end_matches('0'))
+    }
+}
+
+fn parse_iso8601(input: &str, timezone: &FixedOffset) -> Result<DateTime<Utc>, OperationError> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(input) {
+        return Ok(time.to_utc());
+    }
+    let naive = NaiveDateTime::parse_from_str(input, "%Y-%m-%dT%H:%M:%S%.f")
+        .or_else(|_| NaiveDateTime::parse_from_str(input, "%Y-%m-%d %H:%M:%S%.f"))
+        .or_else(|_| NaiveDateTime::parse_from_str(input, "%Y-%m-%dT%H:%M"))
+        .or_else(|_| {
+            NaiveDate::parse_from_str(input, "%Y-%m-%d")
+                .map(|date| date.and_hms_opt(0, 0, 0).unwrap())
+        })
+        .map_err(|_| invalid("ISO 8601 date"))?;
+    let time =
+        timezone.from_local_datetime(&naive).single().ok_or_else(|| invalid("ISO 8601 date"))?;
+    Ok(time.to_utc())
+}
+
+/// Version 1 and 6 UUIDs hold 100ns intervals since 1582-10-15, version 7 Unix milliseconds
+fn uuid_nanos(input: &str) -> Result<i128, OperationError> {
+    let hex: String = input
+        .trim_start_matches("urn:uuid:")
+        .trim_matches(|ch| ch == '{' || ch == '}')
+        .chars()
+        .filter(|ch| *ch != '-')
+        .collect();
+    if hex.len() != 32 {
+        return Err(invalid("UUID"));
+    }
+    let value = u128::from_str_radix(&hex, 16).map_err(|_| invalid("UUID"))?;
+    let field = |shift: u32, bits: u32| (value >> shift) as u64 & ((1 << bits) - 1);
+    let gregorian = |ticks: u64| i128::from(ticks) * 100 + GREGORIAN_EPOCH * NANOS_PER_SECOND;
+    match field(76, 4) {
+        1 => Ok(gregorian(field(64, 12) << 48 | field(80, 16) << 32 | field(96, 32))),
+        6 => Ok(gregorian(field(96, 32) << 28 | field(80, 16) << 12 | field(64, 12))),
+        7 => Ok(i128::from(field(80, 48)) * 1_000_000),
+        version => Err(OperationError::DecodeError {
+            message: format!("UUID version {version} does not hold a timestamp"),
+            offset: None,
+        }),
+    }
+}
+
+/// The first 10 Crockford base32 symbols of a ULID are Unix milliseconds
+fn ulid_nanos(input: &str) -> Result<i128, OperationError> {
+    const SYMBOLS: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+    if input.len() != 26 || !input.is_ascii() {
+        return Err(invalid("ULID"));
+    }
+    let mut millis: u64 = 0;
+    for symbol in input[..10].bytes() {
+        let symbol = symbol.to_ascii_uppercase();
+        let value =
+            SYMBOLS.iter().position(|known| *known == symbol).ok_or_else(|| invalid("ULID"))?;
+        millis = millis << 5 | value as u64;
+    }
+    if millis >> 48 != 0 {
+        return Err(invalid("ULID"));
+    }
+    Ok(i128::from(millis) * 1_000_000)
+}
+
+#[cfg(feature = "registry")]
+impl OperationInfo for TimestampConvert {
+    const ID: &'static str = "timestamp_convert";
+    const NAME: &'static str = "Timestamp Convert";
+    const CATEGORY: Category = Category::Time;
+    const DESCRIPTION: &'static str =
+        "Convert between Unix, Windows, .NET, Apple and WebKit timestamps and date strings";
+
+    fn options_schema() -> Value {
+        let formats = json!([
+            "unix_seconds",
+            "unix_milliseconds",
+            "unix_microseconds",
+            "unix_nanoseconds",
+            "windows_filetime",
+            "dotnet_ticks",
+            "cocoa",
+            "webkit",
+            "uuid",
+            "ulid",
+            "snowflake",
+            "iso8601",
+            "rfc2822"
+        ]);
+        json!({
+            "type": "object",
+            "properties": {
+                "from": { "type": "string", "enum": formats, "default": "unix_seconds" },
+                "to": { "type": "string", "enum": formats, "default": "iso8601" },
+                "timezone": { "type": ["string", "null"], "default": "UTC" },
+                "snowflake_epoch": { "type": ["integer", "null"], "default": SNOWFLAKE_EPOCH }
+            },
+            "required": ["from", "to"]
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn convert(from: TimestampFormat, to: TimestampFormat, input: &str) -> String {
+        let operation = TimestampConvert::new(from, to, None, None);
+        String::from_utf8(operation.execute(input.as_bytes()).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn timestamp_unix_to_iso8601() {
+        use TimestampFormat::*;
+        assert_eq!(convert(UnixSeconds, Iso8601, "1700000000"), "2023-11-14T22:13:20Z");
+        assert_eq!(convert(UnixSeconds, Iso8601, " 1700000000.25\n"), "2023-11-14T22:13:20.250Z");
+        assert_eq!(convert(UnixSeconds, Iso8601, "-1.5"), "1969-12-31T23:59:58.500Z");
+        assert_eq!(convert(UnixMilliseconds, Iso8601, "1700000000123"), "2023-11-14T22:13:20.123Z");
+        assert_eq!(
+            convert(UnixNanoseconds, UnixMicroseconds, "1700000000123456789"),
+            "1700000000123456"
+        );
+    }
+
+    #[test]
+    fn timestamp_epochs() {
+        use TimestampFormat::*;
+        assert_eq!(convert(WindowsFiletime, UnixSeconds, "133443808000000000"), "1699907200");
+        assert_eq!(convert(WindowsFiletime, UnixSeconds, "0x01DA166FB550C000"), "1699907200");
+        assert_eq!(convert(UnixSeconds, WindowsFiletime, "0"), "116444736000000000");
+        assert_eq!(convert(UnixSeconds, DotnetTicks, "0"), "621355968000000000");
+        assert_eq!(convert(DotnetTicks, Iso8601, "0"), "0001-01-01T00:00:00Z");
+        assert_eq!(convert(Cocoa, Iso8601, "721692800.5"), "2023-11-14T22:13:20.500Z");
+        assert_eq!(convert(UnixSeconds, Cocoa, "978307199"), "-1");
+        assert_eq!(convert(Webkit, UnixMilliseconds, "13344473600000000"), "1700000000000");
+    }
+
+    #[test]
+    fn timestamp_identifiers() {
+        use TimestampFormat::*;
+        assert_eq!(
+            convert(Uuid, Iso8601, "c232ab00-9414-11ec-b3c8-9f6bdeced846"),
+            "2022-02-22T19:22:22Z"
+        );
+        assert_eq!(
+            convert(Uuid, Iso8601, "1EC9414C-232A-6B00-B3C8-9F6BDECED846"),
+            "2022-02-22T19:22:22Z"
+        );
+        assert_eq!(
+            convert(Uuid, UnixMilliseconds, "017f22e2-79b0-7cc3-98c4-dc0c0c07398f"),
+            "1645557742000"
+        );
+        assert_eq!(convert(Ulid, UnixMilliseconds, "01ARZ3NDEKTSV4RRFFQ69G5FAV"), "1469922850259");
+        assert_eq!(convert(Snowflake, Iso8601, "1212161535774818304"), "2020-01-01T00:00:05.658Z");
+        let discord =
+            TimestampConvert::new(Snowflake, UnixMilliseconds, None, Some(1_420_070_400_000));
+        assert_eq!(discord.execute(b"175928847299117063").unwrap(), b"1462015105796");
+    }
+
+    #[test]
+    fn timestamp_timezones() {
+        use TimestampFormat::*;
+        let paris = TimestampConvert::new(Iso8601, Iso8601, Some("+01:00".to_string()), None);
+        assert_eq!(paris.execute(b"2024-01-01 12:00:00").unwrap(), b"2024-01-01T12:00:00+01:00");
+        assert_eq!(paris.execute(b"2024-01-01T12:00:00Z").unwrap(), b"2024-01-01T13:00:00+01:00");
+        let to_unix = TimestampConvert::new(Iso8601, UnixSeconds, Some("-0800".to_string()), None);
+        assert_eq!(to_unix.execute(b"1970-01-01").unwrap(), b"28800");
+        assert_eq!(
+            convert(Rfc2822, Iso8601, "Tue, 14 Nov 2023 23:13:20 +0100"),
+            "2023-11-14T22:13:20Z"
+        );
+        let rfc2822 = TimestampConvert::new(UnixSeconds, Rfc2822, Some("+05:30".to_string()), None);
+        assert_eq!(rfc2822.execute(b"1700000000").unwrap(), b"Wed, 15 Nov 2023 03:43:20 +0530");
+    }
+
+    #[test]
+    fn timestamp_errors() {
+        use TimestampFormat::*;
+        let invalid = |from, to, timezone: Option<&str>, input: &str| {
+            TimestampConvert::new(from, to, timezone.map(str::to_string), None)
+                .execute(input.as_bytes())
+                .is_err()
+        };
+        assert!(invalid(UnixSeconds, Iso8601, None, "12a"));
+        assert!(invalid(UnixSeconds, Iso8601, None, "."));
+        assert!(invalid(UnixMilliseconds, Iso8601, None, "99999999999999999999999999"));
+        assert!(invalid(UnixSeconds, Uuid, None, "0"));
+        assert!(invalid(UnixSeconds, Iso8601, Some("CEST"), "0"));
+        assert!(invalid(Uuid, Iso8601, None, "6ba7b810-9dad-41d1-80b4-00c04fd430c8"));
+        assert!(invalid(Ulid, Iso8601, None, "81ARZ3NDEKTSV4RRFFQ69G5FAV"));
+        assert!(invalid(Iso8601, UnixSeconds, None, "yesterday"));
+        assert!(invalid(Ulid, Iso8601, None, "012345678é012345678901234"));
+        assert!(invalid(UnixSeconds, Rfc2822, None, "-99999999999"));
+        assert!(invalid(UnixSeconds, Rfc2822, None, "999999999999"));
+        assert!(TimestampConvert::new(Snowflake, UnixSeconds, None, Some(u64::MAX))
+            .execute(b"175928847299117063")
+            .is_err());
+    }
+}
@@ -0,0 +1,66 @@
+use irongate_encore::{HexDecode, HexEncode, HexFormat, Operation};
+use sqlite_loadable::api::ValueType;
+use sqlite_loadable::ext::{sqlite3_context, sqlite3_value};
+use sqlite_loadable::{api, Error, Result};
+
+/// Operation whose options are fixed when the function is registered
+pub type Fixed = Box<dyn Operation + Send + Sync>;
+
+/// Bytes of a text or blob argument, `None` for SQL NULL
+fn bytes_arg<'a>(values: &[*mut sqlite3_value], col: usize) -> Result<Option<&'a [u8]>> {
+    let value =
+        values.get(col).ok_or_else(|| Error::new_message(format!("expected argument {col}")))?;
+    match api::value_type(value) {
+        ValueType::Null => Ok(None),
+        ValueType::Blob | ValueType::Text => Ok(Some(api::value_blob(value))),
+        _ => Err(Error::new_message(format!("expected argument {col} of type blob or text"))),
+    }
+}
+
+/// Optional text argument such as a hex prefix, missing and NULL both mean none
+fn text_arg(values: &[*mut sqlite3_value], col: usize) -> Result<Option<String>> {
+    match values.get(col) {
+        Some(value) if !matches!(api::value_type(value), ValueType::Null) => {
+            Ok(Some(api::value_text(value)?.to_string()))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Run `operation` on the first argument and return the result as a BLOB, NULL stays NULL
+fn apply(
+    ctx: *mut sqlite3_context,
+    values: &[*mut sqlite3_value],
+    operation: &dyn Operation,
+) -> Result<()> {
+    match bytes_arg(values, 0)? {
+        Some(input) => {
+            let output =
+                operation.execute(input).map_err(|err| Error::new_message(err.to_string()))?;
+            api::result_blob(ctx, &output);
+        }
+        None => api::result_null(ctx),
+    }
+    Ok(())
+}
+
+/// Use as `md5(data)`, `base64_decode(data)`, ...
+pub fn fixed(
+    ctx: *mut sqlite3_context,
+    values: &[*mut sqlite3_value],
+    operation: &Fixed,
+) -> Result<()> {
+    apply(ctx, values, operation.as_ref())
+}
+
+/// Use as `hex_encode(data [, prefix [, delimiter]])`
+pub fn hex_encode(ctx: *mut sqlite3_context, values: &[*mut sqlite3_value]) -> Result<()> {
+    let operation = HexEncode::new(HexFormat::Lower, text_arg(values, 1)?, text_arg(values, 2)?);
+    apply(ctx, values, &operation)
+}
+
+/// Use as `hex_decode(data [, prefix [, delimiter]])`
+pub fn hex_decode(ctx: *mut sqlite3_context, values: &[*mut sqlite3_value]) -> Result<()> {
+    let operation = HexDecode::new(text_arg(values, 1)?, text_arg(values, 2)?);
+    apply(ctx, values, &operation)
+}
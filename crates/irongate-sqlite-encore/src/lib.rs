@@ -0,0 +1,103 @@
+mod function;
+
+use irongate_encore::{
+    Base64Decode, Base64Encode, Base64Format, Blake3Hash, HtmlDecode, Md5Hash, Sha1Hash, Sha2Hash,
+    Sha2Version, UrlDecode,
+};
+use sqlite_loadable::prelude::*;
+use sqlite_loadable::{
+    define_scalar_function, define_scalar_function_with_aux, errors::Result, FunctionFlags,
+};
+
+use crate::function::Fixed;
+
+#[sqlite_entrypoint]
+pub fn sqlite3_irongate_encore_init(db: *mut sqlite3) -> Result<()> {
+    let flags = FunctionFlags::UTF8 | FunctionFlags::DETERMINISTIC;
+
+    let fixed: [(&str, Fixed); 8] = [
+        ("base64_encode", Box::new(Base64Encode::new(Base64Format::Standard, true))),
+        ("base64_decode", Box::new(Base64Decode::new(Base64Format::Standard, true))),
+        ("url_decode", Box::new(UrlDecode::new())),
+        ("html_decode", Box::new(HtmlDecode::new())),
+        ("md5", Box::new(Md5Hash::new())),
+        ("sha1", Box::new(Sha1Hash::new())),
+        ("sha256", Box::new(Sha2Hash::new(Sha2Version::Sha256))),
+        ("blake3", Box::new(Blake3Hash::new())),
+    ];
+    for (name, operation) in fixed {
+        define_scalar_function_with_aux(db, name, 1, function::fixed, flags, operation)?;
+    }
+
+    // hex_encode(data [, prefix [, delimiter]]) and the matching hex_decode
+    for args in 1..=3 {
+        define_scalar_function(db, "hex_encode", args, function::hex_encode, flags)?;
+        define_scalar_function(db, "hex_decode", args, function::hex_decode, flags)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rusqlite::{ffi::sqlite3_auto_extension, types::FromSql, Connection};
+
+    use super::*;
+
+    fn connection() -> Connection {
+        unsafe {
+            sqlite3_auto_extension(Some(std::mem::transmute(
+                sqlite3_irongate_encore_init as *const (),
+            )));
+        }
+        Connection::open_in_memory().unwrap()
+    }
+
+    fn query<T: FromSql>(db: &Connection, sql: &str) -> rusqlite::Result<T> {
+        db.query_row(sql, [], |row| row.get(0))
+    }
+
+    #[test]
+    fn hex_functions() {
+        let db = connection();
+        let encode = |sql| query::<Vec<u8>>(&db, sql).unwrap();
+        assert_eq!(encode("SELECT hex_encode(x'01ab')"), b"01ab");
+        assert_eq!(encode("SELECT hex_encode(x'01ab', '0x')"), b"0x010xab");
+        assert_eq!(encode("SELECT hex_encode(x'01ab', NULL, ':')"), b"01:ab");
+        assert_eq!(encode("SELECT hex_encode(x'01ab', '0x', ' ')"), b"0x01 0xab");
+        assert_eq!(encode("SELECT hex_decode('0x01 0xab', '0x', ' ')"), b"\x01\xab");
+        assert_eq!(encode("SELECT hex_decode(hex_encode('irongate'))"), b"irongate");
+    }
+
+    #[test]
+    fn fixed_functions() {
+        let db = connection();
+        let hex = |sql| String::from_utf8(query::<Vec<u8>>(&db, sql).unwrap()).unwrap();
+        assert_eq!(hex("SELECT hex_encode(md5(''))"), "d41d8cd98f00b204e9800998ecf8427e");
+        assert_eq!(hex("SELECT hex_encode(sha1(''))"), "da39a3ee5e6b4b0d3255bfef95601890afd80709");
+        assert_eq!(
+            hex("SELECT hex_encode(sha256(''))"),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            hex("SELECT hex_encode(blake3(''))"),
+            "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"
+        );
+        assert_eq!(hex("SELECT base64_encode('irongate')"), "aXJvbmdhdGU=");
+        assert_eq!(hex("SELECT base64_decode('aXJvbmdhdGU=')"), "irongate");
+        assert_eq!(hex("SELECT url_decode('a%20b')"), "a b");
+        assert_eq!(hex("SELECT html_decode('&lt;a&gt;')"), "<a>");
+    }
+
+    #[test]
+    fn null_and_errors() {
+        let db = connection();
+        assert_eq!(query::<Option<Vec<u8>>>(&db, "SELECT md5(NULL)").unwrap(), None);
+        assert_eq!(query::<Option<Vec<u8>>>(&db, "SELECT hex_encode(NULL, '0x')").unwrap(), None);
+        let err = query::<Vec<u8>>(&db, "SELECT base64_decode('!!')").unwrap_err();
+        assert!(err.to_string().starts_with("Decode error"), "{err}");
+        assert!(query::<Vec<u8>>(&db, "SELECT hex_decode('zz')").is_err());
+        assert!(query::<Vec<u8>>(&db, "SELECT sha256(1)").is_err());
+        assert!(query::<Vec<u8>>(&db, "SELECT hex_encode(x'01', '', '', '')").is_err());
+    }
+}
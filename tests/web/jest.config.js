@@ -1,4 +1,11 @@
+// Package target the tests import as `encore`, `node` or `web`. Jest runs in Node.js, so the web
+// target is also run in Deno by smoke.mjs
+const target = process.env.ENCORE_TARGET ?? "node";
+
 /** @type {import("jest").Config} */
 export default {
     preset: "ts-jest/presets/js-with-babel-esm",
-};
\ No newline at end of file
+    moduleNameMapper: {
+        "^encore$": `<rootDir>/../../wasm-package/${target}/index.js`,
+    },
+};
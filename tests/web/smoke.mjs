@@ -0,0 +1,17 @@
+// Check that the web target, which Deno resolves, instantiates and runs its worker pool in Deno, which jest cannot host.
+// Usage: deno run --allow-read smoke.mjs
+const { init, applyAsync, Md5Hash } = await import("../../wasm-package/web/index.js");
+
+await init();
+const input = new TextEncoder().encode("irongate");
+const actual = new Md5Hash().applyToHex(input);
+if (actual !== "0d14b5c7855daa1c6f5bf7e4e1fbd762") {
+    throw new Error(`deno: unexpected md5 ${actual}`);
+}
+const digest = await applyAsync(new Md5Hash(), input);
+if (digest.byteLength !== 16) {
+    throw new Error(`deno: unexpected applyAsync digest of ${digest.byteLength} bytes`);
+}
+console.log("deno: ok");
+// The idle pool worker would keep Deno running
+Deno.exit(0);
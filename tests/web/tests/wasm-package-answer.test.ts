@@ -1,4 +1,8 @@
+import { readFileSync } from "fs";
+import { Worker } from "worker_threads";
 import {
+    init,
+    applyAsync,
     UrlEncode,
     UrlDecode,
     Base64Encode,
@@ -12,11 +16,35 @@ import {
     Md5Hash,
     Sha1Hash,
     Sha2Hash,
-} from "../../../wasm-package/encore.js";
+    Md5Hasher,
+    Sha1Hasher,
+    Sha2Hasher,
+    hashStream,
+    Recipe,
+    WasmBuffer,
+    listOperations,
+    createOperation,
+    IrongateError,
+    DecodeError,
+    InvalidOptionsError,
+} from "encore";
+
+// Package target mapped to `encore` by jest.config.js
+const target = process.env.ENCORE_TARGET ?? "node";
+
+beforeAll(async () => {
+    if (target === "node") {
+        // Covers the default path, which reads encore_bg.wasm from disk
+        await init();
+    } else {
+        // The web target fetches its module by default, Node has no fetch for file URLs
+        await init(readFileSync(new URL("../../../wasm-package/web/encore_bg.wasm", import.meta.url)));
+    }
+});
 
 describe("UrlEncoding", () => {
     it("Url encode string", () => {
-        let encoder = new UrlEncode({ non_ascii: true, charset: "e" });
+        let encoder = new UrlEncode({ nonAscii: true, charset: "e" });
         let utf8Encode = new TextEncoder();
         let actual = encoder.apply(utf8Encode.encode("irongate @éé🥖"));
         let expected = utf8Encode.encode("irongat%65 @%C3%A9%C3%A9%F0%9F%A5%96");
@@ -24,6 +52,18 @@ describe("UrlEncoding", () => {
         expect(equal(actual, expected)).toBeTruthy();
     });
 
+    it("Url encode defaults to no encoding", () => {
+        expect(new UrlEncode().applyString("a b@é")).toBe("a b@é");
+        expect(new UrlEncode({}).applyString("a b@é")).toBe("a b@é");
+        expect(new UrlEncode({ charset: " @" }).applyString("a b@é")).toBe("a%20b%40é");
+        expect(new UrlEncode({ nonAscii: true }).applyString("a b@é")).toBe("a b@%C3%A9");
+    });
+
+    it("Url encode accepts the snake_case spelling", () => {
+        expect(new UrlEncode({ non_ascii: true } as any).applyString("é")).toBe("%C3%A9");
+        expect(() => new UrlEncode({ nonascii: true } as any)).toThrow(InvalidOptionsError);
+    });
+
     it("Url decode string", () => {
         let encoder = new UrlDecode();
         let utf8Encode = new TextEncoder();
@@ -54,6 +94,75 @@ describe("Base64 encoding", () => {
 
         expect(equal(actual, expected)).toBeTruthy();
     });
+
+    it("Base64 encode bytes with the standard alphabet", () => {
+        let encoder = new Base64Encode({ format: "standard", pad: true });
+        let utf8Encode = new TextEncoder();
+        let actual = encoder.apply(new Uint8Array([0xfb, 0xff]));
+        let expected = utf8Encode.encode("+/8=");
+
+        expect(equal(actual, expected)).toBeTruthy();
+    });
+
+    it("Base64 encode bytes with the url alphabet", () => {
+        let encoder = new Base64Encode({ format: "url" });
+        let utf8Encode = new TextEncoder();
+        let actual = encoder.apply(new Uint8Array([0xfb, 0xff]));
+        let expected = utf8Encode.encode("-_8=");
+
+        expect(equal(actual, expected)).toBeTruthy();
+    });
+
+    it("Base64 encode bytes without padding", () => {
+        let encoder = new Base64Encode({ format: "url", pad: false });
+        let utf8Encode = new TextEncoder();
+        let actual = encoder.apply(new Uint8Array([0xfb, 0xff]));
+        let expected = utf8Encode.encode("-_8");
+
+        expect(equal(actual, expected)).toBeTruthy();
+    });
+
+    it("Base64 encode bytes as MIME", () => {
+        let encoder = new Base64Encode({ format: "mime" });
+        let utf8Encode = new TextEncoder();
+        let input = new Uint8Array(64).map((_, i) => 0xf0 + (i % 16));
+        let actual = encoder.apply(input);
+        let expected = utf8Encode.encode(
+            "8PHy8/T19vf4+fr7/P3+//Dx8vP09fb3+Pn6+/z9/v/w8fLz9PX29/j5+vv8/f7/8PHy8/T19vf4\r\n" +
+            "+fr7/P3+/w==\r\n"
+        );
+
+        expect(equal(actual, expected)).toBeTruthy();
+    });
+
+    it("Base64 decode bytes with the url alphabet without padding", () => {
+        let decoder = new Base64Decode({ format: "url", pad: false });
+        let utf8Encode = new TextEncoder();
+        let actual = decoder.apply(utf8Encode.encode("-_8"));
+        let expected = new Uint8Array([0xfb, 0xff]);
+
+        expect(equal(actual, expected)).toBeTruthy();
+    });
+
+    it("Base64 decode bytes as MIME", () => {
+        let decoder = new Base64Decode({ format: "mime" });
+        let utf8Encode = new TextEncoder();
+        let actual = decoder.apply(utf8Encode.encode("aXJv\r\nbmdh\r\ndGU=\r\n"));
+        let expected = utf8Encode.encode("irongate");
+
+        expect(equal(actual, expected)).toBeTruthy();
+    });
+
+    it("Base64 rejects an unknown format", () => {
+        expect(() => new Base64Encode({ format: "base85" as any })).toThrow();
+    });
+
+    it("Base64 decode rejects the wrong alphabet", () => {
+        let decoder = new Base64Decode({ format: "standard" });
+        let utf8Encode = new TextEncoder();
+
+        expect(() => decoder.apply(utf8Encode.encode("-_8="))).toThrow();
+    });
 });
 
 describe("Base32Hex encoding", () => {
@@ -174,6 +283,507 @@ describe("Hash", () => {
     });
 });
 
+describe("Operation catalogue", () => {
+    let utf8Encode = new TextEncoder();
+
+    it("Lists the operations with their schema", () => {
+        let operations = listOperations();
+        let hexEncode = operations.find((operation) => operation.id === "hex_encode");
+
+        expect(new Set(operations.map((operation) => operation.id)).size).toBe(operations.length);
+        expect(operations.map((operation) => operation.id)).toContain("sha2_hash");
+        expect(hexEncode).toMatchObject({ name: expect.any(String), category: "encoding" });
+        expect(hexEncode.schema).toMatchObject({ type: "object", required: ["format"] });
+        expect(operations.find((operation) => operation.id === "url_encode").schema.required).toBeUndefined();
+        operations.forEach((operation) => expect(operation.schema.type).toBe("object"));
+    });
+
+    it("Creates operations by id", () => {
+        let operation = createOperation("base64_encode", { format: "url", pad: false });
+
+        expect(operation.id).toBe("base64_encode");
+        expect(operation.applyString("\u{fbff}")).toBe("76-_");
+        expect(equal(operation.apply(new Uint8Array([0xfb, 0xff])), utf8Encode.encode("-_8"))).toBeTruthy();
+        expect(operation.toJSON()).toEqual({ op: "base64_encode", options: { format: "url", pad: false } });
+        expect(createOperation("md5_hash").applyToHex(utf8Encode.encode("irongate"))).toBe(
+            "0d14b5c7855daa1c6f5bf7e4e1fbd762"
+        );
+        expect(createOperation("url_encode", {}).applyString("a b@é")).toBe("a b@é");
+        expect(createOperation("url_encode", { non_ascii: true }).applyString("a b@é")).toBe("a b@%C3%A9");
+        expect(createOperation("url_encode", { nonAscii: true }).applyString("a b@é")).toBe("a b@%C3%A9");
+        expect(new Recipe([{ op: "url_encode", options: { nonAscii: true } }]).applyString("é")).toBe("%C3%A9");
+        expect(new Recipe([{ op: "url_encode", options: { charset: " " } }]).applyString("a b")).toBe("a%20b");
+    });
+
+    it("Rejects unknown operations and invalid options", () => {
+        expect(() => createOperation("rot13", {})).toThrow(IrongateError);
+        expect(() => createOperation("rot13", {})).toThrow(/Unknown operation: rot13/);
+        expect(() => createOperation("sha2_hash", { version: "Sha3" })).toThrow(InvalidOptionsError);
+        expect(() => createOperation("base64_encode", { format: "url", pad: false, padding: true })).toThrow(
+            /unknown option `padding`/
+        );
+        expect(() => createOperation("md5_hash", { format: "hex" })).toThrow(InvalidOptionsError);
+    });
+
+    it("Errors carry the operation id", () => {
+        let operation = createOperation("base64_decode", { format: "standard", pad: true });
+        let error: any;
+        try {
+            operation.applyString("aXJv!mdh");
+        } catch (err) {
+            error = err;
+        }
+
+        expect(error).toBeInstanceOf(DecodeError);
+        expect(error.operation).toBe("base64_decode");
+        expect(error.offset).toBe(4);
+    });
+});
+
+describe("WasmBuffer", () => {
+    let utf8Encode = new TextEncoder();
+
+    it("Views and copies", () => {
+        let buffer = new WasmBuffer(4);
+        expect(buffer.length).toBe(4);
+        expect(equal(buffer.view(), new Uint8Array(4))).toBeTruthy();
+
+        buffer.view().set([1, 2, 3, 4]);
+        buffer.resize(2);
+        expect(equal(buffer.toUint8Array(), new Uint8Array([1, 2]))).toBeTruthy();
+
+        buffer.copyFrom(utf8Encode.encode("irongate"));
+        expect(equal(buffer.view(), utf8Encode.encode("irongate"))).toBeTruthy();
+        expect(new WasmBuffer().length).toBe(0);
+    });
+
+    it("Apply into a reusable output buffer", () => {
+        let input = new WasmBuffer(8);
+        let output = new WasmBuffer();
+        utf8Encode.encodeInto("irongate", input.view());
+
+        expect(new Base64Encode().applyInto(input, output)).toBe(12);
+        expect(equal(output.view(), utf8Encode.encode("aXJvbmdhdGU="))).toBeTruthy();
+
+        expect(new Md5Hash().applyInto(input, output)).toBe(16);
+        expect(equal(output.view(), new Md5Hash().apply(utf8Encode.encode("irongate")))).toBeTruthy();
+    });
+
+    it("Apply in place", () => {
+        let buffer = new WasmBuffer();
+        buffer.copyFrom(utf8Encode.encode("irongate"));
+
+        expect(new HexEncode({ format: "Lower" }).applyInPlace(buffer)).toBe(16);
+        expect(new HexDecode({}).applyInPlace(buffer)).toBe(8);
+        expect(equal(buffer.view(), utf8Encode.encode("irongate"))).toBeTruthy();
+    });
+
+    it("Errors keep or empty the buffers", () => {
+        let buffer = new WasmBuffer();
+        let output = new WasmBuffer(3);
+        buffer.copyFrom(utf8Encode.encode("aXJv!mdh"));
+
+        expect(() => new Base64Decode().applyInPlace(buffer)).toThrow(DecodeError);
+        expect(equal(buffer.view(), utf8Encode.encode("aXJv!mdh"))).toBeTruthy();
+        expect(() => new Base64Decode().applyInto(buffer, output)).toThrow(DecodeError);
+        expect(output.length).toBe(0);
+    });
+
+    it("Recipes apply into buffers", () => {
+        let recipe = new Recipe([{ op: "md5_hash" }, { op: "hex_encode", options: { format: "Lower" } }]);
+        let buffer = new WasmBuffer();
+        buffer.copyFrom(utf8Encode.encode("irongate"));
+
+        expect(recipe.applyInPlace(buffer)).toBe(32);
+        expect(equal(buffer.view(), utf8Encode.encode("0d14b5c7855daa1c6f5bf7e4e1fbd762"))).toBeTruthy();
+        expect(recipe.applyInPlace(buffer)).toBe(32);
+        expect(equal(buffer.view(), utf8Encode.encode("911dfcd34552562d188e481b6bc711eb"))).toBeTruthy();
+
+        let output = new WasmBuffer(4);
+        buffer.copyFrom(utf8Encode.encode("irongate"));
+        expect(recipe.applyInto(buffer, output)).toBe(32);
+        expect(equal(output.view(), utf8Encode.encode("0d14b5c7855daa1c6f5bf7e4e1fbd762"))).toBeTruthy();
+    });
+
+    it("Failing recipes leave buffers like the operation classes", () => {
+        let recipe = new Recipe([
+            { op: "hex_encode", options: { format: "Lower", prefix: "!" } },
+            { op: "base64_decode", options: { format: "standard", pad: true } },
+        ]);
+        let buffer = new WasmBuffer();
+        let output = new WasmBuffer(4);
+        buffer.copyFrom(utf8Encode.encode("iron"));
+
+        expect(() => recipe.applyInPlace(buffer)).toThrow(DecodeError);
+        expect(equal(buffer.view(), utf8Encode.encode("iron"))).toBeTruthy();
+        expect(() => recipe.applyInto(buffer, output)).toThrow(DecodeError);
+        expect(output.length).toBe(0);
+        expect(() => new Recipe([{ op: "hex_decode" }]).applyInto(buffer, output)).toThrow(DecodeError);
+        expect(output.length).toBe(0);
+    });
+});
+
+describe("String helpers", () => {
+    it("Apply to strings", () => {
+        expect(new UrlEncode({ nonAscii: true }).applyString("irongate @é")).toBe("irongate @%C3%A9");
+        expect(new UrlDecode().applyString("irongate @%C3%A9")).toBe("irongate @é");
+        expect(new Base64Encode().applyString("irongate")).toBe("aXJvbmdhdGU=");
+        expect(new HtmlDecode().applyString("&lt;a&gt;")).toBe("<a>");
+    });
+
+    it("Apply and encode the output", () => {
+        let utf8Encode = new TextEncoder();
+        let input = utf8Encode.encode("irongate");
+
+        expect(new Md5Hash().applyToHex(input)).toBe("0d14b5c7855daa1c6f5bf7e4e1fbd762");
+        expect(new Sha1Hash().applyToBase64(input)).toBe("o/jrrLIYZX3tZGokA9nVF5YOI/c=");
+        expect(new HexDecode({}).applyToBase64(utf8Encode.encode("fbff"))).toBe("+/8=");
+    });
+
+    it("Invalid UTF-8 output is strict by default", () => {
+        let decoder = new HexDecode({});
+        let error: any;
+        try {
+            decoder.applyString("6972ff6f");
+        } catch (err) {
+            error = err;
+        }
+
+        expect(error).toBeInstanceOf(DecodeError);
+        expect(error.operation).toBe("hex_decode");
+        expect(error.offset).toBe(2);
+        expect(() => decoder.applyString("6972ff6f", false)).toThrow(DecodeError);
+    });
+
+    it("Invalid UTF-8 output is replaced when lossy", () => {
+        expect(new HexDecode({}).applyString("6972ff6f", true)).toBe("ir\uFFFDo");
+        expect(new Base64Decode().applyString("/w==", true)).toBe("\uFFFD");
+    });
+
+    it("Recipes apply to strings", () => {
+        let recipe = new Recipe([{ op: "md5_hash" }]);
+
+        expect(recipe.applyToHex(new TextEncoder().encode("irongate"))).toBe(
+            "0d14b5c7855daa1c6f5bf7e4e1fbd762"
+        );
+        expect(() => recipe.applyString("irongate")).toThrow(DecodeError);
+    });
+});
+
+describe("Incremental hashing", () => {
+    let utf8Encode = new TextEncoder();
+    let input = utf8Encode.encode("irongate ".repeat(1000));
+    let chunks = [input.subarray(0, 1), input.subarray(1, 4096), input.subarray(4096)];
+
+    it("Chunked updates match the one-shot hash", () => {
+        let pairs: [any, any][] = [
+            [new Md5Hasher(), new Md5Hash()],
+            [new Sha1Hasher(), new Sha1Hash()],
+            [new Sha2Hasher({ version: "Sha224" }), new Sha2Hash({ version: "Sha224" })],
+            [new Sha2Hasher({ version: "Sha512" }), new Sha2Hash({ version: "Sha512" })],
+        ];
+        for (let [hasher, hash] of pairs) {
+            chunks.forEach((chunk) => hasher.update(chunk));
+            expect(equal(hasher.digest(), hash.apply(input))).toBeTruthy();
+        }
+    });
+
+    it("Digest as hex", () => {
+        let hasher = new Md5Hasher();
+        hasher.update(utf8Encode.encode("iron"));
+        hasher.update(utf8Encode.encode("gate"));
+
+        expect(hasher.digestHex()).toBe("0d14b5c7855daa1c6f5bf7e4e1fbd762");
+    });
+
+    it("Digest keeps the state and reset starts over", () => {
+        let hasher = new Sha2Hasher({});
+        hasher.update(utf8Encode.encode("iron"));
+        hasher.digest();
+        hasher.update(utf8Encode.encode("gate"));
+
+        expect(hasher.digestHex()).toBe(
+            "f85b3ccfdb7d51eb7cd37395e7dd423b05da972de615671b147c38a8a4a8642e"
+        );
+
+        hasher.reset();
+        expect(equal(hasher.digest(), new Sha2Hash({}).apply(new Uint8Array()))).toBeTruthy();
+    });
+
+    it("Hash a ReadableStream", async () => {
+        let stream = new ReadableStream<Uint8Array>({
+            start(controller) {
+                chunks.forEach((chunk) => controller.enqueue(chunk));
+                controller.close();
+            },
+        });
+        let digest = await hashStream(new Sha1Hasher(), stream);
+
+        expect(equal(digest, new Sha1Hash().apply(input))).toBeTruthy();
+    });
+
+    it("Sha2Hasher rejects an unknown version", () => {
+        expect(() => new Sha2Hasher({ version: "Sha3" as any })).toThrow(InvalidOptionsError);
+    });
+});
+
+// Node has no Web Worker, so only the pool of the node target runs here, smoke.mjs runs the web
+// target's in Deno
+const describeWorkers = target === "node" ? describe : describe.skip;
+
+describeWorkers("applyAsync", () => {
+    let utf8Encode = new TextEncoder();
+
+    it("Operations serialize to recipe steps", () => {
+        expect(new Md5Hash().toJSON()).toEqual({ op: "md5_hash", options: {} });
+        expect(JSON.parse(JSON.stringify(new Base64Encode({ format: "url", pad: false })))).toEqual({
+            op: "base64_encode",
+            options: { format: "url", pad: false },
+        });
+        expect(new Recipe([{ op: "md5_hash" }]).toJSON()).toEqual([{ op: "md5_hash", options: null }]);
+    });
+
+    it("Matches apply", async () => {
+        let input = utf8Encode.encode("irongate ".repeat(100000));
+        let operation = new Sha2Hash({ version: "Sha512" });
+
+        expect(equal(await applyAsync(operation, input), operation.apply(input))).toBeTruthy();
+    });
+
+    it("Runs recipes and steps", async () => {
+        let input = utf8Encode.encode("irongate");
+        let expected = utf8Encode.encode("0d14b5c7855daa1c6f5bf7e4e1fbd762");
+        let steps = [{ op: "md5_hash" }, { op: "hex_encode", options: { format: "Lower" } }];
+
+        expect(equal(await applyAsync(new Recipe(steps), input), expected)).toBeTruthy();
+        expect(equal(await applyAsync(steps, input), expected)).toBeTruthy();
+    });
+
+    it("Transfers the input when asked", async () => {
+        let input = utf8Encode.encode("irongate");
+        let output = await applyAsync(new Base64Encode(), input, { transfer: true });
+
+        expect(input.byteLength).toBe(0);
+        expect(equal(output, utf8Encode.encode("aXJvbmdhdGU="))).toBeTruthy();
+    });
+
+    it("Rejects with typed errors", async () => {
+        let promise = applyAsync(new Base64Decode(), utf8Encode.encode("aXJv!mdh"));
+
+        await expect(promise).rejects.toBeInstanceOf(DecodeError);
+        await expect(promise).rejects.toMatchObject({ operation: "base64_decode", offset: 4 });
+        let error: any;
+        try {
+            new Base64Decode().applyString("aXJv!mdh");
+        } catch (err) {
+            error = err;
+        }
+        // Same id as the synchronous methods
+        expect(error.operation).toBe("base64_decode");
+    });
+
+    it("Cancels with an AbortSignal", async () => {
+        let controller = new AbortController();
+        // More jobs than the pool has workers, so some of them wait in the queue
+        let inputs = [1, 2, 3, 4, 5, 6].map(() => new Uint8Array(64 * 1024 * 1024));
+        let terminate = Worker.prototype.terminate;
+        let terminated = 0;
+        Worker.prototype.terminate = function () {
+            terminated++;
+            return terminate.call(this);
+        };
+        try {
+            let jobs = inputs.map((input) =>
+                applyAsync(new Sha2Hash({ version: "Sha512" }), input, {
+                    signal: controller.signal,
+                    transfer: true,
+                })
+            );
+            // Running jobs were posted with their input transferred, queued ones still hold it
+            let running = inputs.filter((input) => input.byteLength === 0).length;
+            controller.abort(new Error("cancelled"));
+
+            for (let job of jobs) {
+                await expect(job).rejects.toThrow("cancelled");
+            }
+            expect(running).toBeGreaterThan(0);
+            expect(running).toBeLessThan(inputs.length);
+            expect(inputs.filter((input) => input.byteLength === 0).length).toBe(running);
+            expect(terminated).toBe(running);
+        } finally {
+            Worker.prototype.terminate = terminate;
+        }
+
+        await expect(applyAsync(new Md5Hash(), inputs[5], { signal: controller.signal })).rejects.toThrow(
+            "cancelled"
+        );
+        expect((await applyAsync(new Md5Hash(), utf8Encode.encode("irongate"))).byteLength).toBe(16);
+    });
+
+    it("Replaces a worker that exits while idle", async () => {
+        let postMessage = Worker.prototype.postMessage;
+        let workers = new Set<Worker>();
+        Worker.prototype.postMessage = function (this: Worker, ...args: any[]) {
+            workers.add(this);
+            return postMessage.apply(this, args as any);
+        };
+        try {
+            expect((await applyAsync(new Md5Hash(), utf8Encode.encode("irongate"))).byteLength).toBe(16);
+        } finally {
+            Worker.prototype.postMessage = postMessage;
+        }
+
+        expect(workers.size).toBe(1);
+        for (let worker of workers) {
+            let exited = new Promise((resolve) => worker.once("exit", resolve));
+            await worker.terminate();
+            await exited;
+        }
+        expect((await applyAsync(new Md5Hash(), utf8Encode.encode("irongate"))).byteLength).toBe(16);
+    });
+});
+
+describe("Errors", () => {
+    it("Decode errors are typed with operation and offset", () => {
+        let decoder = new Base64Decode();
+        let utf8Encode = new TextEncoder();
+        let error: any;
+        try {
+            decoder.apply(utf8Encode.encode("aXJv!mdh"));
+        } catch (err) {
+            error = err;
+        }
+
+        expect(error).toBeInstanceOf(DecodeError);
+        expect(error).toBeInstanceOf(IrongateError);
+        expect(error).toBeInstanceOf(Error);
+        expect(error.name).toBe("DecodeError");
+        expect(error.operation).toBe("base64_decode");
+        expect(error.offset).toBe(4);
+    });
+
+    it("Hex decode errors carry the offset", () => {
+        let utf8Encode = new TextEncoder();
+        let offset = (decoder: HexDecode, input: string) => {
+            try {
+                decoder.apply(utf8Encode.encode(input));
+            } catch (err: any) {
+                expect(err).toBeInstanceOf(DecodeError);
+                expect(err.operation).toBe("hex_decode");
+                return err.offset;
+            }
+        };
+
+        expect(offset(new HexDecode({}), "6972zz")).toBe(4);
+        expect(offset(new HexDecode({}), "697")).toBe(2);
+        expect(offset(new HexDecode({ prefix: "0x", delimiter: " " }), "0x69 0xz2")).toBe(7);
+    });
+
+    it("Decode errors without a position have no offset", () => {
+        let decoder = createOperation("pem_decode", {});
+        let utf8Encode = new TextEncoder();
+
+        expect(() => decoder.apply(utf8Encode.encode("irongate"))).toThrow(DecodeError);
+        try {
+            decoder.apply(utf8Encode.encode("irongate"));
+        } catch (err: any) {
+            expect(err.operation).toBe("pem_decode");
+            expect(err.offset).toBeUndefined();
+        }
+    });
+
+    it("Unknown enum strings are rejected", () => {
+        expect(() => new Sha2Hash({ version: "Sha3" as any })).toThrow(InvalidOptionsError);
+        expect(() => new Sha2Hash({ version: "Sha3" as any })).toThrow(/unknown version "Sha3"/);
+        expect(() => new HexEncode({ format: "upper" as any })).toThrow(/expected "Upper" or "Lower"/);
+        expect(() => new Base64Decode({ format: "base85" as any })).toThrow(InvalidOptionsError);
+    });
+
+    it("Unexpected option keys are rejected", () => {
+        expect(() => new HexDecode({ prefix: "0x", delimeter: ":" } as any)).toThrow(
+            /unknown field `delimeter`/
+        );
+        expect(() => new UrlEncode({ non_ascii: true, nonAscii: true } as any)).toThrow(
+            InvalidOptionsError
+        );
+        expect(() => new Base64Encode({ padding: false } as any)).toThrow(InvalidOptionsError);
+    });
+
+    it("Sha2Hash defaults to SHA-256", () => {
+        let encoder = new Sha2Hash({});
+        let utf8Encode = new TextEncoder();
+
+        expect(encoder.apply(utf8Encode.encode("irongate")).byteLength).toBe(32);
+    });
+});
+
+describe("Recipe", () => {
+    it("Runs every step on a single input", () => {
+        let recipe = new Recipe([
+            { op: "md5_hash" },
+            { op: "hex_encode", options: { format: "Lower" } },
+        ]);
+        let utf8Encode = new TextEncoder();
+        let result = recipe.apply(utf8Encode.encode("irongate"));
+        let expected = utf8Encode.encode("0d14b5c7855daa1c6f5bf7e4e1fbd762");
+
+        expect(recipe.length).toBe(2);
+        expect(equal(result.output, expected)).toBeTruthy();
+        expect(result.timings.length).toBe(2);
+        result.timings.forEach((timing) => expect(timing).toBeGreaterThanOrEqual(0));
+    });
+
+    it("Accepts the recipe as a JSON string", () => {
+        let recipe = new Recipe(
+            '[{"op": "base64_decode", "options": {"format": "url", "pad": false}}, {"op": "base64_encode", "options": {"format": "standard", "pad": true}}]'
+        );
+        let utf8Encode = new TextEncoder();
+        let actual = recipe.apply(utf8Encode.encode("-_8")).output;
+        let expected = utf8Encode.encode("+/8=");
+
+        expect(equal(actual, expected)).toBeTruthy();
+    });
+
+    it("An empty recipe returns the input", () => {
+        let recipe = new Recipe([]);
+        let result = recipe.apply(new Uint8Array([1, 2, 3]));
+
+        expect(equal(result.output, new Uint8Array([1, 2, 3]))).toBeTruthy();
+        expect(result.timings.length).toBe(0);
+    });
+
+    it("Rejects invalid steps before running", () => {
+        expect(() => new Recipe([{ op: "md5_hash" }, { op: "rot13" }])).toThrow(InvalidOptionsError);
+        expect(() => new Recipe([{ op: "md5_hash" }, { op: "rot13" }])).toThrow(/step 1 \(rot13\)/);
+        expect(() => new Recipe([{ op: "sha2_hash", options: { version: "Sha3" } }])).toThrow(
+            /step 0 \(sha2_hash\)/
+        );
+        expect(() => new Recipe([{ op: "md5_hash", option: {} } as any])).toThrow(InvalidOptionsError);
+        let misspelled = [{ op: "base64_encode", options: { format: "url", pad: false, padding: true } }];
+        expect(() => new Recipe(misspelled)).toThrow(InvalidOptionsError);
+        expect(() => new Recipe(misspelled)).toThrow(/step 0 \(base64_encode\): .*unknown option `padding`/);
+        expect(() => new Recipe("[{")).toThrow(InvalidOptionsError);
+    });
+
+    it("Step errors carry the operation id", () => {
+        let recipe = new Recipe([
+            { op: "md5_hash" },
+            { op: "base64_decode", options: { format: "standard", pad: true } },
+        ]);
+        let recipeDecode = new Recipe([{ op: "base64_decode", options: { format: "standard", pad: true } }]);
+        let utf8Encode = new TextEncoder();
+
+        expect(() => recipe.apply(utf8Encode.encode("irongate"))).toThrow(DecodeError);
+        try {
+            recipeDecode.apply(utf8Encode.encode("aXJv!mdh"));
+        } catch (err: any) {
+            expect(err).toBeInstanceOf(DecodeError);
+            expect(err.operation).toBe("base64_decode");
+            expect(err.offset).toBe(4);
+        }
+    });
+});
+
 const equal = (buf1: Uint8Array, buf2: Uint8Array) => {
     if (buf1.byteLength != buf2.byteLength) return false;
     for (var i = 0; i != buf1.byteLength; i++) {